@@ -0,0 +1,80 @@
+//! Micro-benchmarks of the matrix module on tournament adjacency matrices.
+//!
+//! Save a baseline before changing the multiplication code and compare against it afterwards:
+//!
+//! ```text
+//! cargo bench --bench matrix -- --save-baseline main
+//! cargo bench --bench matrix -- --baseline main
+//! ```
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+
+use ping_pong::graph::generator::{generate_ping_pong, seeded_rng};
+use ping_pong::matrix::generic_strassen::{connect_4_matrices, explode_matrix_to_4};
+use ping_pong::matrix::matrix::Matrix;
+use ping_pong::matrix::mixed_mul::mixed_mul;
+use ping_pong::matrix::naive_mul::naive_mul;
+use ping_pong::matrix::strassen_mul::strassen_mul;
+use ping_pong::ping_pong::ping_pong_solver::MultiplicationAlgorithm;
+
+/// Power-of-two sizes and their odd neighbours, which take the padding path of Strassen.
+const SIZES: [usize; 6] = [15, 16, 31, 32, 63, 64];
+
+fn tournament(size: usize, stream: u64) -> Matrix<i32> {
+    generate_ping_pong(size as i32, 0.5, &mut seeded_rng(size as u64, stream)).adj_matrix
+}
+
+fn multiplication(c: &mut Criterion) {
+    let methods: [(&str, MultiplicationAlgorithm); 3] =
+        [("naive_mul", naive_mul), ("strassen_mul", strassen_mul), ("mixed_mul", mixed_mul)];
+    for (name, mul) in methods {
+        let mut group = c.benchmark_group(name);
+        group.sample_size(20);
+        for size in SIZES {
+            let a = tournament(size, 0);
+            group.bench_with_input(BenchmarkId::from_parameter(size), &a, |b, a| b.iter(|| mul(black_box(a), black_box(a))));
+        }
+        group.finish();
+    }
+}
+
+fn addition(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Matrix::add");
+    for size in SIZES {
+        let (a, b) = (tournament(size, 0), tournament(size, 1));
+        let mut result = Matrix::new(size, size);
+        group.bench_function(BenchmarkId::from_parameter(size), |bench| bench.iter(|| Matrix::add(black_box(&a), black_box(&b), &mut result)));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("Matrix::sub");
+    for size in SIZES {
+        let (a, b) = (tournament(size, 0), tournament(size, 1));
+        let mut result = Matrix::new(size, size);
+        group.bench_function(BenchmarkId::from_parameter(size), |bench| bench.iter(|| Matrix::sub(black_box(&a), black_box(&b), &mut result)));
+    }
+    group.finish();
+}
+
+fn quadrants(c: &mut Criterion) {
+    let mut group = c.benchmark_group("explode_matrix_to_4");
+    for size in SIZES {
+        let m = tournament(size, 0);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &m, |b, m| b.iter(|| explode_matrix_to_4(black_box(m))));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("connect_4_matrices");
+    for size in SIZES {
+        let (m11, m12, m21, m22) = explode_matrix_to_4(&tournament(size, 0));
+        let compress = size % 2 == 1;
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| connect_4_matrices(black_box(&m11), black_box(&m12), black_box(&m21), black_box(&m22), compress))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, multiplication, addition, quadrants);
+criterion_main!(benches);
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ping_pong::format::graph_format::{read_graphs, GraphFormat};
+
+fuzz_target!(|data: &[u8]| {
+    // Errors are fine, panics are not. Every graph is read so later blocks are parsed as well.
+    for graph in read_graphs(GraphFormat::Json, data).flatten() {
+        let _ = graph.to_string();
+    }
+});
@@ -1,6 +1,11 @@
-use clap::{Arg, ArgAction, ArgMatches, Command};
-use crate::console_arguments::{ConsoleArguments, GenerateArguments, SolveArguments};
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
+use crate::console_arguments::{BenchArguments, BenchGraphs, ConsoleArguments, ConvertArguments, ExportArguments, ExportFormat, GenerateArguments, GenerationMethod, SolveArguments, StatsArguments, VerifyArguments};
 use crate::console_arguments::MultiplicationMethod::{Naive, Strassen, Mixed};
+use ping_pong::format::dot_format::DotLayout;
+use ping_pong::format::graph_format::GraphFormat;
+use ping_pong::format::solution_format::SolutionFormat;
+use ping_pong::graph::graph::DrawSemantics;
+use ping_pong::graph::strength_model::StrengthModel;
 
 pub fn parse_console_arguments() -> ConsoleArguments {
     let matches = construct_command().get_matches();
@@ -38,17 +43,67 @@ fn construct_arguments(matches: ArgMatches) -> ConsoleArguments {
                 { Mixed };
 
             let verbose = opts.get_flag("verbose");
+            let partial = opts.get_flag("partial");
+            let margin = opts.get_one::<i32>("margin").copied();
+            let explain = opts.get_flag("explain");
+            let cross_check = opts.get_flag("cross-check");
+            let draws = draw_semantics(opts);
 
-            ConsoleArguments::Solve(SolveArguments{ input_files, output_file, mul_method, verbose })
+            let input_format = input_format(opts);
+
+            let output_format = opts.get_one::<String>("output-format")
+                .and_then(|name| SolutionFormat::from_name(name))
+                .expect("has default");
+
+            ConsoleArguments::Solve(SolveArguments{ input_files, input_format, output_file, output_format, mul_method, verbose, partial, margin, explain, cross_check, draws })
         }
         Some(("generate", opts)) => {
             let sizes: Vec<i32> = if opts.contains_id("sizes") {
                 opts
-                    .get_many::<i32>("sizes")
+                    .get_many::<Vec<i32>>("sizes")
                     .expect("contains_id")
-                    .map(|i| *i)
+                    .flatten()
+                    .copied()
                     .collect()
-            } else { unreachable!("Argument is required") };
+            } else { Vec::new() };
+
+            let method = if opts.contains_id("scores") {
+                let scores = opts
+                    .get_many::<i32>("scores")
+                    .expect("contains_id")
+                    .copied()
+                    .collect();
+                GenerationMethod::ScoreSequence { scores, randomize: opts.get_flag("randomize") }
+            } else {
+                match opts.get_one::<String>("family").map(|s| s.as_str()) {
+                    None | Some("random") => {
+                        let probability = *opts.get_one::<f64>("probability").expect("has default");
+                        GenerationMethod::Random { probability }
+                    }
+                    Some(family @ ("bradley-terry" | "elo")) => {
+                        let model = if family == "elo" { StrengthModel::Elo } else { StrengthModel::BradleyTerry };
+                        let spread = opts.get_one::<f64>("spread").copied().unwrap_or(model.default_spread());
+                        GenerationMethod::League { model, spread }
+                    }
+                    Some("transitive") => GenerationMethod::Transitive,
+                    Some("regular") => GenerationMethod::Regular,
+                    Some("doubly-regular") => GenerationMethod::DoublyRegular,
+                    Some("paley") => GenerationMethod::Paley,
+                    Some("rotational") => {
+                        let connection_set = opts
+                            .get_many::<usize>("connection-set")
+                            .expect("required by family")
+                            .copied()
+                            .collect();
+                        GenerationMethod::Rotational { connection_set }
+                    }
+                    Some("upset") => {
+                        let upsets = *opts.get_one::<usize>("upsets").expect("required by family");
+                        GenerationMethod::Upset { upsets }
+                    }
+                    Some(_) => unreachable!("Restricted by value parser")
+                }
+            };
 
             let output_file: String = if opts.contains_id("output") {
                 opts
@@ -57,12 +112,224 @@ fn construct_arguments(matches: ArgMatches) -> ConsoleArguments {
                     .into()
             } else { unreachable!("Argument is required") };
 
-            ConsoleArguments::Generate(GenerateArguments{ sizes, output_file })
+            let seed = opts.get_one::<u64>("seed").copied();
+            let count = *opts.get_one::<usize>("count").expect("has default");
+            let metadata = opts.get_flag("metadata");
+            let output_format = graph_format(opts, "output-format");
+
+            ConsoleArguments::Generate(GenerateArguments{ sizes, method, seed, count, metadata, output_file, output_format })
+        }
+        Some(("stats", opts)) => {
+            let input_files: Vec<String> = if opts.contains_id("input") {
+                opts
+                    .get_many::<String>("input")
+                    .expect("contains_id")
+                    .map(|s| s.into())
+                    .collect()
+            } else { unreachable!("Argument is required") };
+
+            let draws = draw_semantics(opts);
+
+            let input_format = input_format(opts);
+
+            ConsoleArguments::Stats(StatsArguments{ input_files, input_format, draws })
+        }
+        Some(("export", opts)) => {
+            let input_files: Vec<String> = if opts.contains_id("input") {
+                opts
+                    .get_many::<String>("input")
+                    .expect("contains_id")
+                    .map(|s| s.into())
+                    .collect()
+            } else { unreachable!("Argument is required") };
+
+            let output_file: String = opts
+                .get_one::<String>("output")
+                .expect("required")
+                .into();
+
+            let format = match opts.get_one::<String>("format").map(|s| s.as_str()) {
+                Some("dot") => ExportFormat::Dot,
+                _ => unreachable!("Restricted by value parser")
+            };
+            let layout = match opts.get_one::<String>("layout").map(|s| s.as_str()) {
+                Some("components") => DotLayout::Components,
+                Some("scores") => DotLayout::Scores,
+                _ => DotLayout::Free,
+            };
+            let transitive_reduction = opts.get_flag("transitive-reduction");
+            let draws = draw_semantics(opts);
+
+            let input_format = input_format(opts);
+
+            ConsoleArguments::Export(ExportArguments{ input_files, input_format, output_file, format, layout, transitive_reduction, draws })
+        }
+        Some(("convert", opts)) => {
+            let input_files: Vec<String> = if opts.contains_id("input") {
+                opts
+                    .get_many::<String>("input")
+                    .expect("contains_id")
+                    .map(|s| s.into())
+                    .collect()
+            } else { unreachable!("Argument is required") };
+
+            let output_file: String = opts
+                .get_one::<String>("output")
+                .expect("required")
+                .into();
+
+            let input_format = input_format(opts);
+            let output_format = graph_format(opts, "output-format");
+
+            ConsoleArguments::Convert(ConvertArguments{ input_files, input_format, output_file, output_format })
+        }
+        Some(("verify", opts)) => {
+            let input_files: Vec<String> = if opts.contains_id("input") {
+                opts
+                    .get_many::<String>("input")
+                    .expect("contains_id")
+                    .map(|s| s.into())
+                    .collect()
+            } else { unreachable!("Argument is required") };
+
+            let results_file: String = opts
+                .get_one::<String>("results")
+                .expect("required")
+                .into();
+
+            let margin = opts.get_one::<i32>("margin").copied();
+            let draws = draw_semantics(opts);
+
+            let input_format = input_format(opts);
+
+            ConsoleArguments::Verify(VerifyArguments{ input_files, input_format, results_file, margin, draws })
+        }
+        Some(("bench", opts)) => {
+            let graphs = if opts.contains_id("sizes") {
+                let sizes = opts
+                    .get_many::<Vec<i32>>("sizes")
+                    .expect("contains_id")
+                    .flatten()
+                    .copied()
+                    .collect();
+                BenchGraphs::Sizes { sizes, seed: opts.get_one::<u64>("seed").copied() }
+            } else {
+                let input_files = opts
+                    .get_many::<String>("input")
+                    .expect("required by group")
+                    .map(|s| s.into())
+                    .collect();
+                BenchGraphs::Files { input_files, input_format: input_format(opts) }
+            };
+
+            let methods = opts
+                .get_many::<String>("methods")
+                .expect("has default")
+                .map(|name| match name.as_str() {
+                    "naive" => Naive,
+                    "strassen" => Strassen,
+                    _ => Mixed,
+                })
+                .collect();
+            let repeat = *opts.get_one::<u64>("repeat").expect("has default") as usize;
+            let csv_file = opts.get_one::<String>("csv").cloned();
+
+            ConsoleArguments::Bench(BenchArguments{ graphs, methods, repeat, csv_file })
         }
         _ => unreachable!()
     }
 }
 
+fn input_format(opts: &ArgMatches) -> GraphFormat {
+    graph_format(opts, "input-format")
+}
+
+fn graph_format(opts: &ArgMatches, id: &str) -> GraphFormat {
+    opts.get_one::<String>(id)
+        .and_then(|name| GraphFormat::from_name(name))
+        .expect("has default")
+}
+
+fn input_format_arg() -> Arg {
+    Arg::new("input-format")
+        .long("input-format")
+        .help("format of the input files: adjacency matrices, `winner loser` lines one per game, csv or json")
+        .value_parser(GraphFormat::NAMES)
+        .default_value("matrix")
+        .action(ArgAction::Set)
+        .num_args(1)
+}
+
+fn output_format_arg() -> Arg {
+    Arg::new("output-format")
+        .long("output-format")
+        .help("format of the output file")
+        .value_parser(GraphFormat::NAMES)
+        .default_value("matrix")
+        .action(ArgAction::Set)
+        .num_args(1)
+}
+
+fn margin_arg() -> Arg {
+    Arg::new("margin")
+        .long("margin")
+        .help("Count only wins of a series by at least this many games, for inputs with win counts")
+        .value_parser(clap::value_parser!(i32).range(1..))
+        .action(ArgAction::Set)
+        .num_args(1)
+}
+
+fn draw_semantics(opts: &ArgMatches) -> DrawSemantics {
+    match opts.get_one::<String>("draws").map(|s| s.as_str()) {
+        Some("mutual") => DrawSemantics::Mutual,
+        Some("half") => DrawSemantics::Half,
+        _ => DrawSemantics::Ignore,
+    }
+}
+
+fn draws_arg() -> Arg {
+    Arg::new("draws")
+        .long("draws")
+        .help("How draws marked with '=' count: ignore them, as mutual wins or as half a point for the score")
+        .value_parser(["ignore", "mutual", "half"])
+        .default_value("ignore")
+        .action(ArgAction::Set)
+        .num_args(1)
+}
+
+/// Accepts a single size `n`, an inclusive range `a..b` or a range with a step `a..b:step`.
+fn parse_sizes(value: &str) -> Result<Vec<i32>, String> {
+    let parse = |s: &str| s.trim().parse::<i32>().map_err(|_| format!("{} is not a number", s));
+
+    let positive = |size: i32| if size >= 1 { Ok(size) } else { Err(format!("size {} must be positive", size)) };
+
+    let Some((start, rest)) = value.split_once("..") else {
+        return Ok(vec![positive(parse(value)?)?]);
+    };
+    let (end, step) = match rest.split_once(':') {
+        Some((end, step)) => (parse(end)?, parse(step)?),
+        None => (parse(rest)?, 1),
+    };
+    if step <= 0 {
+        return Err(format!("step {} must be positive", step));
+    }
+    let start = positive(parse(start)?)?;
+    if end < start {
+        return Err(format!("range {}..{} is empty", start, end));
+    }
+    Ok((start..=end).step_by(step as usize).collect())
+}
+
+fn parse_probability(value: &str) -> Result<f64, String> {
+    let p: f64 = value.parse().map_err(|_| format!("{} is not a number", value))?;
+    if (0.0..=1.0).contains(&p) { Ok(p) } else { Err(format!("{} is not in range 0..=1", p)) }
+}
+
+fn parse_spread(value: &str) -> Result<f64, String> {
+    let spread: f64 = value.parse().map_err(|_| format!("{} is not a number", value))?;
+    if spread.is_finite() && spread > 0.0 { Ok(spread) } else { Err(format!("{} is not a positive number", spread)) }
+}
+
 fn construct_command() -> Command {
     Command::new("ping-pong")
         .about("Algorithm to solve the ping-pong problem")
@@ -80,19 +347,114 @@ fn construct_command() -> Command {
                     Arg::new("sizes")
                         .short('s')
                         .long("sizes")
-                        .help("sizes of graphs to generate")
-                        .value_parser(clap::value_parser!(i32))
+                        .help("sizes of graphs to generate, either numbers or ranges like 10..1000:10")
+                        .value_parser(parse_sizes)
                         .action(ArgAction::Set)
                         .num_args(1..),
                 )
+                .arg(
+                    Arg::new("scores")
+                        .long("scores")
+                        .help("score sequence of the single tournament to generate, e.g. 3,2,2,1,2")
+                        .value_parser(clap::value_parser!(i32))
+                        .value_delimiter(',')
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .group(
+                    ArgGroup::new("graphs")
+                        .args(["sizes", "scores"])
+                        .required(true)
+                )
+                .arg(
+                    Arg::new("family")
+                        .short('f')
+                        .long("family")
+                        .help("family of tournaments to generate (default: random)")
+                        .value_parser(["random", "transitive", "regular", "doubly-regular", "paley", "rotational", "upset", "bradley-terry", "elo"])
+                        .conflicts_with("scores")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("probability")
+                        .short('p')
+                        .long("probability")
+                        .help("probability that the player with the higher index wins a game of the random family")
+                        .value_parser(parse_probability)
+                        .default_value("0.5")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("spread")
+                        .long("spread")
+                        .help("spread of player ratings in bradley-terry (default 1.0) and elo (default 200) families")
+                        .value_parser(parse_spread)
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("connection-set")
+                        .long("connection-set")
+                        .help("differences won by each vertex of a rotational tournament, e.g. 1,2,4")
+                        .value_parser(clap::value_parser!(usize))
+                        .value_delimiter(',')
+                        .required_if_eq("family", "rotational")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("upsets")
+                        .long("upsets")
+                        .help("number of games reversed in a transitive tournament of the upset family")
+                        .value_parser(clap::value_parser!(usize))
+                        .required_if_eq("family", "upset")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("count")
+                        .short('c')
+                        .long("count")
+                        .help("number of graphs to generate for each size")
+                        .value_parser(clap::value_parser!(usize))
+                        .default_value("1")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("metadata")
+                        .long("metadata")
+                        .help("Precede every graph with a comment describing how it was generated")
+                        .action(ArgAction::SetTrue)
+                        .num_args(0)
+                )
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .help("seed of the random number generator, a random one is chosen and printed if not present")
+                        .value_parser(clap::value_parser!(u64))
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("randomize")
+                        .long("randomize")
+                        .help("Sample among tournaments with the given scores by random 3-cycle reversals")
+                        .requires("scores")
+                        .action(ArgAction::SetTrue)
+                        .num_args(0)
+                )
                 .arg(
                     Arg::new("output")
                         .long("output")
                         .short('o')
-                        .help("output file to store the generated graphs to")
+                        .help("output file to store the generated graphs to, `-` for the standard output")
                         .action(ArgAction::Set)
                         .num_args(1),
-                ),
+                )
+                .arg(output_format_arg()),
         )
         // Command to run solver
         .subcommand(
@@ -107,8 +469,9 @@ fn construct_command() -> Command {
                         .long("input")
                         .action(ArgAction::Set)
                         .num_args(1..)
-                        .help("input files with one or more graphs to solve"),
+                        .help("input files with one or more graphs to solve, `-` for the standard input"),
                 )
+                .arg(input_format_arg())
                 .arg(
                     Arg::new("output")
                         .short('o')
@@ -117,6 +480,16 @@ fn construct_command() -> Command {
                         .help("optional output file to store the solutions in. Solutions are passed to standard output if option not present")
                         .num_args(0..=1),
                 )
+                .arg(
+                    Arg::new("output-format")
+                        .long("output-format")
+                        .help("format of the stored solutions, records of all formats but plain include the source file, \
+                        graph index, vertex count, multiplication method and elapsed time")
+                        .value_parser(SolutionFormat::NAMES)
+                        .default_value("plain")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
                 .arg(
                     Arg::new("strassen")
                         .long("strassen")
@@ -151,6 +524,259 @@ fn construct_command() -> Command {
                         .help("Print input graphs and their solutions side by side")
                         .action(ArgAction::SetTrue)
                         .num_args(0),
+                )
+                .arg(
+                    Arg::new("partial")
+                        .long("partial")
+                        .help("Report kings under every (certain) and under some (possible) outcome of unplayed games marked with '-'. \
+                        Output files get both lists separated by '|'")
+                        .action(ArgAction::SetTrue)
+                        .num_args(0),
+                )
+                .arg(margin_arg())
+                .arg(
+                    Arg::new("explain")
+                        .long("explain")
+                        .help("Print how every king reaches the other players, with the margin of each series")
+                        .conflicts_with("partial")
+                        .action(ArgAction::SetTrue)
+                        .num_args(0),
+                )
+                .arg(
+                    Arg::new("cross-check")
+                        .long("cross-check")
+                        .help("Compare A² and the kings of every multiplication method and abort on the first difference")
+                        .action(ArgAction::SetTrue)
+                        .num_args(0),
+                )
+                .arg(draws_arg()),
+        )
+        // Command to print score statistics
+        .subcommand(
+            Command::new("stats")
+                .short_flag('t')
+                .long_flag("stats")
+                .about("Print scores and king statistics of graphs.")
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("input")
+                        .short('i')
+                        .long("input")
+                        .action(ArgAction::Set)
+                        .num_args(1..)
+                        .help("input files with one or more graphs to analyse, `-` for the standard input"),
+                )
+                .arg(input_format_arg())
+                .arg(draws_arg()),
+        )
+        // Command to export graphs for drawing
+        .subcommand(
+            Command::new("export")
+                .short_flag('e')
+                .long_flag("export")
+                .about("Export graphs with their kings highlighted for drawing.")
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("input")
+                        .short('i')
+                        .long("input")
+                        .action(ArgAction::Set)
+                        .num_args(1..)
+                        .help("input files with one or more graphs to export, `-` for the standard input"),
+                )
+                .arg(input_format_arg())
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .required(true)
+                        .action(ArgAction::Set)
+                        .help("output file to store the exported graphs in, `-` for the standard output")
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .help("format of the export, Graphviz DOT")
+                        .value_parser(["dot"])
+                        .default_value("dot")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("layout")
+                        .long("layout")
+                        .help("put vertices in layers by strongly connected component or by score, strongest first")
+                        .value_parser(["free", "components", "scores"])
+                        .default_value("free")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("transitive-reduction")
+                        .long("transitive-reduction")
+                        .help("Draw only the wins needed to keep who reaches whom")
+                        .action(ArgAction::SetTrue)
+                        .num_args(0),
+                )
+                .arg(draws_arg()),
+        )
+        // Command to convert graphs between formats
+        .subcommand(
+            Command::new("convert")
+                .short_flag('c')
+                .long_flag("convert")
+                .about("Convert graphs between formats.")
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("input")
+                        .short('i')
+                        .long("input")
+                        .action(ArgAction::Set)
+                        .num_args(1..)
+                        .help("input files with one or more graphs to convert, `-` for the standard input"),
+                )
+                .arg(input_format_arg())
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .required(true)
+                        .action(ArgAction::Set)
+                        .help("output file to store the converted graphs in, `-` for the standard output")
+                        .num_args(1),
+                )
+                .arg(output_format_arg()),
+        )
+        // Command to check solutions
+        .subcommand(
+            Command::new("verify")
+                .long_flag("verify")
+                .about("Check claimed kings against the graphs they were computed for.")
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("input")
+                        .short('i')
+                        .long("input")
+                        .action(ArgAction::Set)
+                        .num_args(1..)
+                        .help("input files with the graphs the results belong to, `-` for the standard input"),
+                )
+                .arg(input_format_arg())
+                .arg(
+                    Arg::new("results")
+                        .short('r')
+                        .long("results")
+                        .required(true)
+                        .action(ArgAction::Set)
+                        .help("results written by solve, one line of king names per graph, with '|' between certain and possible kings")
+                        .num_args(1),
+                )
+                .arg(margin_arg())
+                .arg(draws_arg()),
+        )
+        // Command to compare multiplication methods
+        .subcommand(
+            Command::new("bench")
+                .short_flag('b')
+                .long_flag("bench")
+                .about("Time the multiplication methods and the whole solve on graphs of different sizes.")
+                .arg_required_else_help(true)
+                .arg(
+                    Arg::new("sizes")
+                        .short('s')
+                        .long("sizes")
+                        .help("sizes of random tournaments to benchmark, either numbers or ranges like 100..1000:100")
+                        .value_parser(parse_sizes)
+                        .action(ArgAction::Set)
+                        .num_args(1..),
+                )
+                .arg(
+                    Arg::new("input")
+                        .short('i')
+                        .long("input")
+                        .action(ArgAction::Set)
+                        .num_args(1..)
+                        .help("input files with graphs to benchmark, `-` for the standard input"),
+                )
+                .group(
+                    ArgGroup::new("graphs")
+                        .args(["sizes", "input"])
+                        .required(true)
+                )
+                .arg(input_format_arg())
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .help("seed of the random tournaments, a random one is chosen and printed if not present")
+                        .value_parser(clap::value_parser!(u64))
+                        .requires("sizes")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("methods")
+                        .long("methods")
+                        .help("multiplication methods to compare")
+                        .value_parser(["naive", "strassen", "mixed"])
+                        .value_delimiter(',')
+                        .default_value("naive,strassen,mixed")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("repeat")
+                        .short('r')
+                        .long("repeat")
+                        .help("number of timed runs of every method on every graph, after one warm-up run")
+                        .value_parser(clap::value_parser!(u64).range(1..))
+                        .default_value("5")
+                        .action(ArgAction::Set)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new("csv")
+                        .long("csv")
+                        .help("file to also store the timings in as CSV, `-` for the standard output")
+                        .action(ArgAction::Set)
+                        .num_args(1),
                 ),
         )
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::console::{parse_sizes, parse_spread};
+
+    #[test]
+    pub fn parses_single_size() {
+        assert_eq!(Ok(vec![7]), parse_sizes("7"));
+    }
+
+    #[test]
+    pub fn parses_size_ranges() {
+        assert_eq!(Ok(vec![3, 4, 5]), parse_sizes("3..5"));
+        assert_eq!(Ok(vec![10, 20, 30]), parse_sizes("10..30:10"));
+        assert_eq!(Ok(vec![10, 20]), parse_sizes("10..29:10"));
+    }
+
+    #[test]
+    pub fn rejects_malformed_sizes() {
+        assert!(parse_sizes("x").is_err());
+        assert!(parse_sizes("1..").is_err());
+        assert!(parse_sizes("1..5:0").is_err());
+        assert!(parse_sizes("0").is_err());
+        assert!(parse_sizes("-3").is_err());
+        assert!(parse_sizes("-3..5").is_err());
+        assert!(parse_sizes("10..5").is_err());
+    }
+
+    #[test]
+    pub fn spread_is_finite_and_positive() {
+        assert_eq!(Ok(1000.0), parse_spread("1000"));
+        assert!(parse_spread("0").is_err());
+        assert!(parse_spread("-1").is_err());
+        assert!(parse_spread("nan").is_err());
+        assert!(parse_spread("inf").is_err());
+    }
+}
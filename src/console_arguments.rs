@@ -1,27 +1,166 @@
+use std::fmt;
+use ping_pong::format::dot_format::DotLayout;
+use ping_pong::format::graph_format::GraphFormat;
+use ping_pong::format::solution_format::SolutionFormat;
+use ping_pong::graph::graph::DrawSemantics;
+use ping_pong::graph::strength_model::StrengthModel;
+use ping_pong::matrix::mixed_mul::mixed_mul;
+use ping_pong::matrix::naive_mul::naive_mul;
+use ping_pong::matrix::strassen_mul::strassen_mul;
+use ping_pong::ping_pong::ping_pong_solver::MultiplicationAlgorithm;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MultiplicationMethod  {
     Naive,
     Strassen,
     Mixed
 }
 
+impl MultiplicationMethod {
+    /// Every available method, the first one serves as reference for cross-checking.
+    pub const ALL: [MultiplicationMethod; 3] = [MultiplicationMethod::Naive, MultiplicationMethod::Strassen, MultiplicationMethod::Mixed];
+
+    pub fn algorithm(&self) -> MultiplicationAlgorithm {
+        match self {
+            MultiplicationMethod::Naive => naive_mul,
+            MultiplicationMethod::Strassen => strassen_mul,
+            MultiplicationMethod::Mixed => mixed_mul,
+        }
+    }
+}
+
+impl fmt::Display for MultiplicationMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultiplicationMethod::Naive => write!(f, "naive"),
+            MultiplicationMethod::Strassen => write!(f, "strassen"),
+            MultiplicationMethod::Mixed => write!(f, "mixed"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SolveArguments {
     pub input_files: Vec<String>,
+    pub input_format: GraphFormat,
     pub output_file: Option<String>,
+    pub output_format: SolutionFormat,
     pub mul_method: MultiplicationMethod,
-    pub verbose: bool
+    pub verbose: bool,
+    pub partial: bool,
+    pub margin: Option<i32>,
+    pub explain: bool,
+    pub cross_check: bool,
+    pub draws: DrawSemantics
+}
+
+#[derive(Debug)]
+pub enum GenerationMethod {
+    Random { probability: f64 },
+    League { model: StrengthModel, spread: f64 },
+    Transitive,
+    Regular,
+    DoublyRegular,
+    Paley,
+    Rotational { connection_set: Vec<usize> },
+    Upset { upsets: usize },
+    ScoreSequence { scores: Vec<i32>, randomize: bool }
+}
+
+/// Describes the method as `key=value` pairs for the metadata headers of generated graphs.
+impl fmt::Display for GenerationMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |values: Vec<String>| values.join(",");
+        match self {
+            GenerationMethod::Random { probability } => write!(f, "family=random probability={}", probability),
+            GenerationMethod::League { model: StrengthModel::BradleyTerry, spread } => write!(f, "family=bradley-terry spread={}", spread),
+            GenerationMethod::League { model: StrengthModel::Elo, spread } => write!(f, "family=elo spread={}", spread),
+            GenerationMethod::Transitive => write!(f, "family=transitive"),
+            GenerationMethod::Regular => write!(f, "family=regular"),
+            GenerationMethod::DoublyRegular => write!(f, "family=doubly-regular"),
+            GenerationMethod::Paley => write!(f, "family=paley"),
+            GenerationMethod::Rotational { connection_set } =>
+                write!(f, "family=rotational connection-set={}", join(connection_set.iter().map(|d| d.to_string()).collect())),
+            GenerationMethod::Upset { upsets } => write!(f, "family=upset upsets={}", upsets),
+            GenerationMethod::ScoreSequence { scores, randomize } =>
+                write!(f, "scores={} randomize={}", join(scores.iter().map(|s| s.to_string()).collect()), randomize),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct GenerateArguments {
     pub sizes: Vec<i32>,
-    pub output_file: String
+    pub method: GenerationMethod,
+    pub seed: Option<u64>,
+    pub count: usize,
+    pub metadata: bool,
+    pub output_file: String,
+    pub output_format: GraphFormat
+}
+
+#[derive(Debug)]
+pub struct StatsArguments {
+    pub input_files: Vec<String>,
+    pub input_format: GraphFormat,
+    pub draws: DrawSemantics
+}
+
+#[derive(Debug)]
+pub enum ExportFormat {
+    Dot
+}
+
+#[derive(Debug)]
+pub struct ExportArguments {
+    pub input_files: Vec<String>,
+    pub input_format: GraphFormat,
+    pub output_file: String,
+    pub format: ExportFormat,
+    pub layout: DotLayout,
+    pub transitive_reduction: bool,
+    pub draws: DrawSemantics
+}
+
+#[derive(Debug)]
+pub struct ConvertArguments {
+    pub input_files: Vec<String>,
+    pub input_format: GraphFormat,
+    pub output_file: String,
+    pub output_format: GraphFormat
+}
+
+#[derive(Debug)]
+pub struct VerifyArguments {
+    pub input_files: Vec<String>,
+    pub input_format: GraphFormat,
+    pub results_file: String,
+    pub margin: Option<i32>,
+    pub draws: DrawSemantics
+}
+
+/// Graphs to benchmark: random tournaments of the given sizes or graphs from files.
+#[derive(Debug)]
+pub enum BenchGraphs {
+    Sizes { sizes: Vec<i32>, seed: Option<u64> },
+    Files { input_files: Vec<String>, input_format: GraphFormat },
+}
+
+#[derive(Debug)]
+pub struct BenchArguments {
+    pub graphs: BenchGraphs,
+    pub methods: Vec<MultiplicationMethod>,
+    pub repeat: usize,
+    pub csv_file: Option<String>
 }
 
 #[derive(Debug)]
 pub enum ConsoleArguments {
     Solve(SolveArguments),
-    Generate(GenerateArguments)
+    Generate(GenerateArguments),
+    Stats(StatsArguments),
+    Export(ExportArguments),
+    Convert(ConvertArguments),
+    Verify(VerifyArguments),
+    Bench(BenchArguments)
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
-use std::fs::File;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::io;
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 
 use crate::graph::graph::Graph;
 use crate::matrix::matrix::Matrix;
@@ -8,56 +9,433 @@ use crate::matrix::matrix::Matrix;
 pub struct DataParser {
 }
 
+/// Single entry of an adjacency matrix in the input.
+#[derive(Clone, Copy, PartialEq)]
+enum Cell {
+    Wins(i32),
+    Unplayed,
+    Draw,
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Wins(wins) => write!(f, "{}", wins),
+            Cell::Unplayed => write!(f, "{}", DataParser::UNPLAYED),
+            Cell::Draw => write!(f, "{}", DataParser::DRAW),
+        }
+    }
+}
+
 impl DataParser {
 
-    fn add_graph(graphs: &mut Vec<Graph>, matrix_rows: &Vec<i32>, rows: usize) {
-        let cols = matrix_rows.len() / rows;
-
-        let data: Vec<_> = (0..cols)
-            .map(|col| matrix_rows
-                .iter()
-                .skip(col)
-                .step_by(rows)
-                .cloned()
-                .collect::<Vec<_>>())
-            .flatten()
-            .collect();
+    /// Marks a game that has not been played yet.
+    const UNPLAYED: &'static str = "-";
+    /// Marks a drawn game.
+    const DRAW: &'static str = "=";
+    /// Starts the row naming the players of a matrix, which the writers always mark so that
+    /// numeric names cannot be taken for a row of the matrix.
+    pub const PLAYERS: &'static str = "players:";
 
-        graphs.push(Graph::new(Matrix::with_flat_data(cols, data)));
+    fn parse_cell(token: &str) -> Option<Cell> {
+        match token {
+            Self::UNPLAYED => Some(Cell::Unplayed),
+            Self::DRAW => Some(Cell::Draw),
+            _ => token.parse().ok().map(Cell::Wins),
+        }
+    }
+
+    /// Checks that names can be written to every format and read back: they are unique, have none of
+    /// the whitespace, commas and `|` separating them in matrices and results, and do not start like a comment.
+    pub fn check_names(names: &[String]) -> Result<(), io::Error> {
+        let mut seen = HashSet::new();
+        for name in names {
+            if name.is_empty() || name.starts_with('#') || name.contains(|c: char| c.is_whitespace() || c == ',' || c == '|') {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("name `{}` is empty, starts with # or contains whitespace, a comma or |", name)
+                ));
+            }
+            if !seen.insert(name) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("name `{}` is given to more than one player", name)
+                ));
+            }
+        }
+        Ok(())
     }
 
-    pub fn parse_graph_input(input_file: &str) -> Result<Vec<Graph>, io::Error> {
-        let file = match File::open(input_file) {
-            Ok(file) => file,
-            Err(e) => return Err(e),
+    fn build_graph(labels: Option<Vec<String>>, rows: &[Vec<Cell>]) -> Result<Graph, io::Error> {
+        let n = rows.len();
+        if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != n) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("row {} has {} entries but the matrix has {} rows", i + 1, row.len(), n)
+            ));
+        }
+        if let Some(labels) = &labels {
+            if labels.len() != n {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("header names {} players but the matrix has {}", labels.len(), n)
+                ));
+            }
+            Self::check_names(labels)?;
+        }
+        if n == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "matrix has no players"));
+        }
+
+        let mut counts = Matrix::new(n, n);
+        for (row, cells) in rows.iter().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                match cell {
+                    Cell::Wins(wins) if *wins < 0 => return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("game between {} and {} has a negative number of wins", row + 1, col + 1)
+                    )),
+                    Cell::Wins(wins) => counts[col][row] = *wins,
+                    _ => {}
+                }
+            }
+        }
+
+        // Both entries of a pair have to tell the same: two counts, or the same mark twice
+        let mut unplayed = Vec::new();
+        let mut draws = Vec::new();
+        for (row, cells) in rows.iter().enumerate() {
+            for (col, mirrored) in rows.iter().enumerate().skip(row + 1) {
+                match (cells[col], mirrored[row]) {
+                    (Cell::Wins(_), Cell::Wins(_)) => {}
+                    (Cell::Unplayed, Cell::Unplayed) => unplayed.push((row, col)),
+                    (Cell::Draw, Cell::Draw) => draws.push((row, col)),
+                    (entry, mirrored) => return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("game between {} and {} is entered as `{}` and `{}`", row + 1, col + 1, entry, mirrored)
+                    )),
+                }
+            }
+        }
+
+        // Plain tournaments are kept as they are, series results are turned into win counts.
+        let single_games = (0..n).all(|col| (0..n).all(|row| {
+            let (won, lost) = (counts[col][row], counts[row][col]);
+            won <= 1 && !(won > 0 && lost > 0)
+        }));
+        let mut graph = if single_games {
+            Graph::with_unplayed(counts, unplayed)
+        } else {
+            Graph::from_win_counts(counts, unplayed)
         };
+        for (a, b) in draws {
+            graph.set_draw(a, b);
+        }
+        graph.labels = labels;
+        Ok(graph)
+    }
+
+    /// Reads whitespace-separated matrices lazily, one graph at a time. A row of names marked with
+    /// `players:` may precede each matrix, unmarked names are only told apart from a row of the
+    /// matrix if one of them is not a matrix entry.
+    pub fn parse_graph_input<R: BufRead>(reader: R) -> MatrixGraphs<R> {
+        MatrixGraphs { lines: reader.lines(), tokens: |line| line.split_whitespace().collect() }
+    }
+
+    /// Reads matrices with comma-separated entries, each optionally preceded by a header row of names.
+    pub fn parse_csv_input<R: BufRead>(reader: R) -> MatrixGraphs<R> {
+        MatrixGraphs { lines: reader.lines(), tokens: |line| line.split(',').map(str::trim).collect() }
+    }
+
+    /// Builds a single graph from rows of matrix entries as they appear in the text formats.
+    pub fn graph_from_rows(labels: Option<Vec<String>>, rows: &[Vec<String>]) -> Result<Graph, io::Error> {
+        let rows = rows.iter()
+            .map(|row| Self::parse_row(row.iter().map(String::as_str)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::build_graph(labels, &rows)
+    }
+
+    fn parse_row<'a>(tokens: impl IntoIterator<Item = &'a str>) -> Result<Vec<Cell>, io::Error> {
+        tokens.into_iter()
+            .map(|token| Self::parse_cell(token).ok_or_else(|| io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("`{}` is not a matrix entry", token)
+            )))
+            .collect()
+    }
+
+    /// Reads tournaments written as one `winner loser` line per game, separated by blank lines.
+    /// Players are numbered in order of appearance and named after the input.
+    /// Pairs of players without a game between them are marked as unplayed.
+    pub fn parse_match_input<R: BufRead>(reader: R) -> MatchGraphs<R> {
+        MatchGraphs { lines: reader.lines().enumerate() }
+    }
+
+    /// Builds a graph from single games given as `(location, winner, loser)`, where the location
+    /// describes where the game was recorded for error messages. Players in `players` keep their
+    /// position, all others are numbered in order of appearance.
+    pub fn graph_from_matches(players: Vec<String>, matches: &[(String, String, String)]) -> Result<Graph, io::Error> {
+        let mut names = players;
+        let mut ids: HashMap<String, usize> = names.iter().enumerate().map(|(i, name)| (name.clone(), i)).collect();
+        for (_, winner, loser) in matches {
+            for name in [winner, loser] {
+                if !ids.contains_key(name) {
+                    ids.insert(name.clone(), names.len());
+                    names.push(name.clone());
+                }
+            }
+        }
 
-        let reader = BufReader::new(file);
+        let n = names.len();
+        // Where the game between two players was recorded, indexed by the smaller id first.
+        let mut recorded: HashMap<(usize, usize), &str> = HashMap::new();
+        let mut graph = Graph::new(Matrix::new(n, n));
+        for (location, winner, loser) in matches {
+            let (w, l) = (ids[winner], ids[loser]);
+            if w == l {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {} cannot play against themselves", location, winner)
+                ));
+            }
+            if let Some(previous) = recorded.insert((w.min(l), w.max(l)), location) {
+                let problem = if graph.beats(w, l) { "duplicates" } else { "contradicts" };
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {} {} {} the result on {}", location, winner, loser, problem, previous)
+                ));
+            }
+            graph.set_winner(w, l);
+        }
 
-        let mut graphs = Vec::new();
+        let unplayed = (0..n)
+            .flat_map(|a| (0..a).map(move |b| (b, a)))
+            .filter(|pair| !recorded.contains_key(pair))
+            .collect();
+        Self::check_names(&names)?;
+        let mut graph = Graph::with_unplayed(graph.adj_matrix, unplayed);
+        graph.labels = Some(names);
+        Ok(graph)
+    }
+}
+
+/// Iterator over the graphs of a matrix input, reading one block of lines per graph.
+pub struct MatrixGraphs<R> {
+    lines: io::Lines<R>,
+    tokens: fn(&str) -> Vec<&str>,
+}
+
+impl<R: BufRead> Iterator for MatrixGraphs<R> {
+    type Item = Result<Graph, io::Error>;
 
-        let mut matrix_rows = Vec::new();
-        let mut rows = 0;
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut labels = None;
+        let mut rows: Vec<Vec<Cell>> = Vec::new();
+        // The rest of a block with a malformed row is skipped, so the next call starts at the next block
+        let mut error = None;
 
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                if line.eq("") {
-                    Self::add_graph(&mut graphs, &matrix_rows, rows);
-                    matrix_rows = Vec::new();
-                    rows = 0;
+        for line in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            // Comments, e.g. metadata headers of generated graphs
+            if line.starts_with('#') {
+                continue;
+            }
+            if line.trim().is_empty() {
+                if rows.is_empty() && labels.is_none() && error.is_none() {
                     continue;
                 }
-                let row: Vec<i32> = line
-                    .split_whitespace()
-                    .filter_map(|s| s.parse().ok())
-                    .collect();
-                matrix_rows.extend_from_slice(&row);
-                rows += 1;
+                break;
+            }
+            if error.is_some() {
+                continue;
+            }
+            let tokens = (self.tokens)(&line);
+            // A first row that is marked or not made of matrix entries names the players
+            if rows.is_empty() && labels.is_none() {
+                if tokens.first() == Some(&DataParser::PLAYERS) {
+                    labels = Some(tokens[1..].iter().map(|s| s.to_string()).collect());
+                    continue;
+                }
+                if tokens.iter().any(|s| DataParser::parse_cell(s).is_none()) {
+                    labels = Some(tokens.into_iter().map(String::from).collect());
+                    continue;
+                }
+            }
+            match DataParser::parse_row(tokens) {
+                Ok(row) => rows.push(row),
+                Err(e) => error = Some(e),
+            }
+        }
+        if let Some(e) = error {
+            return Some(Err(e));
+        }
+        if !rows.is_empty() || labels.is_some() {
+            return Some(DataParser::build_graph(labels, &rows));
+        }
+        None
+    }
+}
+
+/// Iterator over the tournaments of a match list, reading one block of lines per tournament.
+pub struct MatchGraphs<R> {
+    lines: std::iter::Enumerate<io::Lines<R>>,
+}
+
+impl<R: BufRead> Iterator for MatchGraphs<R> {
+    type Item = Result<Graph, io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut matches = Vec::new();
+
+        for (i, line) in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            let line_number = i + 1;
+            if line.starts_with('#') {
+                continue;
+            }
+            let players: Vec<&str> = line.split_whitespace().collect();
+            match players[..] {
+                [] => {
+                    if !matches.is_empty() {
+                        return Some(DataParser::graph_from_matches(Vec::new(), &matches));
+                    }
+                }
+                [winner, loser] => matches.push((format!("line {}", line_number), winner.to_string(), loser.to_string())),
+                _ => return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: expected `winner loser`, got `{}`", line_number, line)
+                ))),
             }
         }
-        if rows != 0 {
-            Self::add_graph(&mut graphs, &matrix_rows, rows);
+        if !matches.is_empty() {
+            return Some(DataParser::graph_from_matches(Vec::new(), &matches));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data_parser::{DataParser, MatrixGraphs};
+
+    #[test]
+    pub fn reads_blocks_with_names_and_comments() {
+        let input = "# first\na b c\n0 1 0\n0 0 1\n1 0 0\n\n0 1\n0 0\n";
+
+        let graphs: Vec<_> = DataParser::parse_graph_input(input.as_bytes()).map(Result::unwrap).collect();
+
+        assert_eq!(2, graphs.len());
+        assert_eq!("b", graphs[0].vertex_name(1));
+        assert!(graphs[0].beats(0, 1) && graphs[0].beats(2, 0));
+        assert_eq!(None, graphs[1].labels);
+    }
+
+    #[test]
+    pub fn marked_headers_may_be_numbers() {
+        let plain = DataParser::parse_graph_input("players: 1 10 2\n0 1 0\n0 0 1\n1 0 0\n".as_bytes()).next().unwrap().unwrap();
+        let csv = DataParser::parse_csv_input("players:,1,10,2\n0,1,0\n0,0,1\n1,0,0\n".as_bytes()).next().unwrap().unwrap();
+
+        assert_eq!("10", plain.vertex_name(1));
+        assert_eq!(3, plain.vertices());
+        assert_eq!(format!("{}", plain), format!("{}", csv));
+        assert_eq!(format!("{}", plain), "players: 1 10 2\n 0  1  0 \n 0  0  1 \n 1  0  0 \n");
+    }
+
+    #[test]
+    pub fn names_are_unique_and_separable() {
+        let errors = [
+            DataParser::parse_csv_input("Alice Smith,Bob\n0,1\n0,0\n".as_bytes()).next(),
+            DataParser::parse_csv_input("players:,a,\n0,1\n0,0\n".as_bytes()).next(),
+            DataParser::parse_graph_input("players: a a\n0 1\n0 0\n".as_bytes()).next(),
+            DataParser::parse_match_input("a,b c\n".as_bytes()).next(),
+            DataParser::parse_match_input("a #b\n".as_bytes()).next(),
+            DataParser::parse_match_input("a|b c\n".as_bytes()).next(),
+        ].map(|graph| graph.unwrap().unwrap_err().to_string());
+
+        assert_eq!(errors, [
+            "name `Alice Smith` is empty, starts with # or contains whitespace, a comma or |",
+            "name `` is empty, starts with # or contains whitespace, a comma or |",
+            "name `a` is given to more than one player",
+            "name `a,b` is empty, starts with # or contains whitespace, a comma or |",
+            "name `#b` is empty, starts with # or contains whitespace, a comma or |",
+            "name `a|b` is empty, starts with # or contains whitespace, a comma or |",
+        ]);
+    }
+
+    #[test]
+    pub fn graphs_are_parsed_one_at_a_time() {
+        // The second block is malformed, the first one is still returned before the error.
+        let mut graphs = DataParser::parse_graph_input("0 1\n0 0\n\na b\n0\n".as_bytes());
+
+        assert!(graphs.next().unwrap().is_ok());
+        assert!(graphs.next().unwrap().is_err());
+        assert!(graphs.next().is_none());
+    }
+
+    #[test]
+    pub fn csv_matches_whitespace_format() {
+        let csv = DataParser::parse_csv_input("a, b\n0, -\n-, 0\n".as_bytes()).next().unwrap().unwrap();
+        let plain = DataParser::parse_graph_input("a b\n0 -\n- 0\n".as_bytes()).next().unwrap().unwrap();
+
+        assert_eq!(format!("{}", plain), format!("{}", csv));
+    }
+
+    #[test]
+    pub fn match_lists_reject_repeated_games() {
+        let graphs: Vec<_> = DataParser::parse_match_input("alice bob\nbob carol\n\nx y\n".as_bytes()).map(Result::unwrap).collect();
+        assert_eq!(2, graphs.len());
+        assert!(!graphs[0].is_played(0, 2));
+
+        let error = DataParser::parse_match_input("a b\nb a\n".as_bytes()).next().unwrap().unwrap_err();
+        assert_eq!("line 2: b a contradicts the result on line 1", error.to_string());
+        let error = DataParser::parse_match_input("a b\na b\n".as_bytes()).next().unwrap().unwrap_err();
+        assert_eq!("line 2: a b duplicates the result on line 1", error.to_string());
+    }
+
+    #[test]
+    pub fn malformed_blocks_are_errors() {
+        type Parser = fn(&'static [u8]) -> MatrixGraphs<&'static [u8]>;
+        let cases: [(Parser, &str, &str); 9] = [
+            // Found by the parse_csv fuzz target
+            (DataParser::parse_csv_input, "-\n-", "row 1 has 1 entries but the matrix has 2 rows"),
+            (DataParser::parse_graph_input, "0 1\n0\n", "row 2 has 1 entries but the matrix has 2 rows"),
+            (DataParser::parse_graph_input, "a b\n\n0\n", "header names 2 players but the matrix has 0"),
+            (DataParser::parse_graph_input, "a b\n", "header names 2 players but the matrix has 0"),
+            (DataParser::parse_graph_input, "0 1\n- x\n", "`x` is not a matrix entry"),
+            (DataParser::parse_graph_input, "0 -1\n1 0\n", "game between 1 and 2 has a negative number of wins"),
+            (DataParser::parse_graph_input, "0 - 1\n= 0 1\n0 0 0\n", "game between 1 and 2 is entered as `-` and `=`"),
+            (DataParser::parse_graph_input, "0 -\n0 0\n", "game between 1 and 2 is entered as `-` and `0`"),
+            (DataParser::parse_csv_input, "0,1\n=,0\n", "game between 1 and 2 is entered as `1` and `=`"),
+        ];
+
+        for (parse, input, message) in cases {
+            let error = parse(input.as_bytes()).next().unwrap().unwrap_err();
+            assert_eq!(message, error.to_string());
         }
-        Ok(graphs)
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn reading_resumes_after_malformed_block() {
+        let mut graphs = DataParser::parse_graph_input("0 1\n0 x\n1 0\n\n0\n".as_bytes());
+
+        assert_eq!("`x` is not a matrix entry", graphs.next().unwrap().unwrap_err().to_string());
+        assert_eq!(1, graphs.next().unwrap().unwrap().vertices());
+        assert!(graphs.next().is_none());
+    }
+
+    #[test]
+    pub fn whitespace_lines_separate_blocks() {
+        let graphs: Vec<_> = DataParser::parse_graph_input("0 1\r\n0 0\r\n \r\n0\n".as_bytes()).collect();
+
+        assert_eq!(2, graphs.len());
+        assert!(graphs.iter().all(Result::is_ok));
+        // Found by the parse_matrix fuzz target
+        assert!(DataParser::parse_graph_input(" ".as_bytes()).next().is_none());
+    }
+}
@@ -0,0 +1,6 @@
+pub mod binary_format;
+pub mod dot_format;
+pub mod graph_format;
+pub mod json_format;
+pub mod solution_format;
+pub mod stream;
@@ -0,0 +1,248 @@
+use std::io;
+use std::io::{Read, Write};
+
+use crate::data_parser::DataParser;
+use crate::graph::graph::Graph;
+use crate::matrix::matrix::Matrix;
+
+/// Every graph is one record:
+///
+/// | bytes | content                                                              |
+/// |-------|----------------------------------------------------------------------|
+/// | 4     | magic `PPTB`                                                         |
+/// | 1     | format version                                                       |
+/// | 1     | flags, bit 0 set if names follow                                     |
+/// | 4     | number of vertices `n`, little endian                                |
+/// | ...   | if named, every name as its byte length (4 bytes LE) and UTF-8 bytes |
+/// | ...   | upper triangle, one bit per pair `i < j` in row order, set if `i` beats `j`, least significant bit first |
+/// | 4     | CRC-32 of all previous bytes of the record, little endian            |
+///
+/// Records follow each other directly, so files can be concatenated.
+const MAGIC: &[u8; 4] = b"PPTB";
+const VERSION: u8 = 1;
+const FLAG_LABELS: u8 = 1;
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Number of bytes holding one bit for every pair of `n` vertices.
+fn triangle_bytes(n: usize) -> usize {
+    (n * n.saturating_sub(1) / 2).div_ceil(8)
+}
+
+/// Writes a tournament as one record. Graphs with unplayed games, draws or
+/// series results cannot be represented with one bit per pair and are rejected.
+pub fn write_binary<W: Write>(out: &mut W, graph: &Graph) -> Result<(), io::Error> {
+    let n = graph.vertices();
+    if !graph.is_tournament() || graph.win_counts.is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the binary format only holds tournaments with a single game per pair"
+        ));
+    }
+    let vertices = u32::try_from(n).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "too many vertices"))?;
+
+    let mut record = Vec::with_capacity(14 + triangle_bytes(n));
+    record.extend_from_slice(MAGIC);
+    record.push(VERSION);
+    record.push(if graph.labels.is_some() { FLAG_LABELS } else { 0 });
+    record.extend_from_slice(&vertices.to_le_bytes());
+    if let Some(labels) = &graph.labels {
+        for label in labels {
+            record.extend_from_slice(&(label.len() as u32).to_le_bytes());
+            record.extend_from_slice(label.as_bytes());
+        }
+    }
+
+    let mut bits = vec![0u8; triangle_bytes(n)];
+    let mut k = 0;
+    for i in 0..n {
+        for j in i + 1..n {
+            if graph.beats(i, j) {
+                bits[k / 8] |= 1 << (k % 8);
+            }
+            k += 1;
+        }
+    }
+    record.extend_from_slice(&bits);
+    record.extend_from_slice(&crc32(&record).to_le_bytes());
+
+    out.write_all(&record)
+}
+
+/// Reads records lazily, one graph at a time.
+pub fn read_binary<R: Read>(reader: R) -> BinaryGraphs<R> {
+    BinaryGraphs { reader, index: 0 }
+}
+
+pub struct BinaryGraphs<R> {
+    reader: R,
+    index: usize,
+}
+
+impl<R: Read> BinaryGraphs<R> {
+    /// Reads exactly `len` bytes, appending them to the record. Memory grows with the bytes
+    /// actually read, so a corrupt length cannot allocate more than the input holds.
+    fn read_into(&mut self, record: &mut Vec<u8>, len: usize) -> Result<(), io::Error> {
+        let read = (&mut self.reader).take(len as u64).read_to_end(record)?;
+        if read != len {
+            return Err(invalid(format!("record {} ends unexpectedly", self.index)));
+        }
+        Ok(())
+    }
+
+    fn read_u32(&mut self, record: &mut Vec<u8>) -> Result<u32, io::Error> {
+        self.read_into(record, 4)?;
+        let bytes = record[record.len() - 4..].try_into().expect("4 bytes");
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn read_record(&mut self, first: u8) -> Result<Graph, io::Error> {
+        let mut record = vec![first];
+        self.read_into(&mut record, 5)?;
+        if &record[..4] != MAGIC {
+            return Err(invalid(format!("record {} is not a binary graph", self.index)));
+        }
+        if record[4] != VERSION {
+            return Err(invalid(format!("record {} has unsupported version {}", self.index, record[4])));
+        }
+        let flags = record[5];
+        if flags & !FLAG_LABELS != 0 {
+            return Err(invalid(format!("record {} has unknown flags {:#04x}", self.index, flags)));
+        }
+        let n = self.read_u32(&mut record)? as usize;
+
+        let labels = if flags & FLAG_LABELS != 0 {
+            let mut labels = Vec::new();
+            for _ in 0..n {
+                let len = self.read_u32(&mut record)? as usize;
+                let start = record.len();
+                self.read_into(&mut record, len)?;
+                let label = String::from_utf8(record[start..].to_vec())
+                    .map_err(|_| invalid(format!("record {} has a name that is not UTF-8", self.index)))?;
+                labels.push(label);
+            }
+            Some(labels)
+        } else {
+            None
+        };
+
+        let start = record.len();
+        self.read_into(&mut record, triangle_bytes(n))?;
+        let expected = crc32(&record);
+        if self.read_u32(&mut record)? != expected {
+            return Err(invalid(format!("record {} fails its checksum", self.index)));
+        }
+        if let Some(labels) = &labels {
+            DataParser::check_names(labels).map_err(|e| invalid(format!("record {}: {}", self.index, e)))?;
+        }
+
+        let bits = &record[start..start + triangle_bytes(n)];
+        let mut adj = Matrix::new(n, n);
+        let mut k = 0;
+        for i in 0..n {
+            for j in i + 1..n {
+                if bits[k / 8] & (1 << (k % 8)) != 0 {
+                    adj[j][i] = 1;
+                } else {
+                    adj[i][j] = 1;
+                }
+                k += 1;
+            }
+        }
+        let mut graph = Graph::new(adj);
+        graph.labels = labels;
+        Ok(graph)
+    }
+}
+
+impl<R: Read> Iterator for BinaryGraphs<R> {
+    type Item = Result<Graph, io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut first = [0u8];
+        let graph = match self.reader.read(&mut first) {
+            Ok(0) => return None,
+            Ok(_) => self.read_record(first[0]),
+            Err(e) => Err(e),
+        };
+        self.index += 1;
+        Some(graph)
+    }
+}
+
+/// CRC-32 as used by zip and PNG.
+fn crc32(bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut c = i as u32;
+            let mut k = 0;
+            while k < 8 {
+                c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+                k += 1;
+            }
+            table[i] = c;
+            i += 1;
+        }
+        table
+    };
+    !bytes.iter().fold(!0u32, |crc, byte| TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::binary_format::{crc32, read_binary, write_binary};
+    use crate::graph::generator::{generate_ping_pong, seeded_rng};
+
+    #[test]
+    pub fn crc32_check_value() {
+        assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+    }
+
+    #[test]
+    pub fn records_round_trip() {
+        let mut rng = seeded_rng(7, 0);
+        let mut named = generate_ping_pong(5, 0.5, &mut rng);
+        named.labels = Some(["a", "b", "c", "d", "e"].map(String::from).to_vec());
+        let plain = generate_ping_pong(13, 0.5, &mut rng);
+
+        let mut out = Vec::new();
+        write_binary(&mut out, &named).unwrap();
+        write_binary(&mut out, &plain).unwrap();
+        let read: Vec<_> = read_binary(out.as_slice()).map(Result::unwrap).collect();
+
+        assert_eq!(2, read.len());
+        assert_eq!(format!("{}", named), format!("{}", read[0]));
+        assert_eq!(format!("{}", plain), format!("{}", read[1]));
+    }
+
+    #[test]
+    pub fn corruption_is_detected() {
+        let graph = generate_ping_pong(6, 0.5, &mut seeded_rng(1, 0));
+        let mut out = Vec::new();
+        write_binary(&mut out, &graph).unwrap();
+
+        let mut flipped = out.clone();
+        flipped[10] ^= 1;
+        assert!(read_binary(flipped.as_slice()).next().unwrap().is_err());
+
+        let truncated = &out[..out.len() - 1];
+        assert!(read_binary(truncated).next().unwrap().is_err());
+    }
+
+    #[test]
+    pub fn names_are_checked() {
+        let mut graph = generate_ping_pong(2, 0.5, &mut seeded_rng(1, 0));
+        graph.labels = Some(["Alice Smith", "Bob"].map(String::from).to_vec());
+        let mut out = Vec::new();
+        write_binary(&mut out, &graph).unwrap();
+
+        assert_eq!(
+            "record 0: name `Alice Smith` is empty, starts with # or contains whitespace, a comma or |",
+            read_binary(out.as_slice()).next().unwrap().unwrap_err().to_string()
+        );
+    }
+}
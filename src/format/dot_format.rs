@@ -0,0 +1,126 @@
+use std::io;
+use std::io::Write;
+
+use crate::graph::graph::{DrawSemantics, Graph};
+use crate::graph::reachability::{strong_components, transitive_reduction};
+
+/// How vertices are grouped into layers of the drawing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DotLayout {
+    /// Leave the layout to Graphviz.
+    Free,
+    /// One layer per strongly connected component, in order of dominance.
+    Components,
+    /// One layer per score, highest first.
+    Scores,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DotOptions {
+    pub layout: DotLayout,
+    /// Draw only the edges needed to keep who reaches whom, without draws.
+    pub transitive_reduction: bool,
+    /// How draws count for the scores of the `Scores` layout.
+    pub draws: DrawSemantics,
+}
+
+/// Writes a graph as a DOT digraph with edges from winners to losers and its `kings` filled.
+/// `index` numbers the digraphs of one output.
+pub fn write_dot<W: Write>(out: &mut W, index: usize, graph: &Graph, kings: &[i32], options: &DotOptions) -> Result<(), io::Error> {
+    let n = graph.vertices();
+    let wins = graph.dominance_matrix(1);
+    let edges = if options.transitive_reduction { transitive_reduction(&wins) } else { wins.clone() };
+
+    writeln!(out, "digraph \"graph {}\" {{", index + 1)?;
+    writeln!(out, "  node [shape=circle];")?;
+    for v in 0..n {
+        let style = if kings.contains(&(v as i32)) { ", style=filled, fillcolor=gold" } else { "" };
+        writeln!(out, "  v{} [label=\"{}\"{}];", v, escape(&graph.vertex_name(v)), style)?;
+    }
+
+    let layer = match options.layout {
+        DotLayout::Free => None,
+        DotLayout::Components => Some(strong_components(&wins)),
+        DotLayout::Scores => {
+            let scores = graph.scores(options.draws);
+            let mut distinct = scores.clone();
+            distinct.sort_by(|a, b| b.total_cmp(a));
+            distinct.dedup();
+            Some(scores.iter().map(|s| distinct.iter().position(|d| d == s).expect("score of a vertex")).collect())
+        }
+    };
+    if let Some(layer) = layer {
+        let layers = layer.iter().max().map_or(0, |max| max + 1);
+        for l in 0..layers {
+            let vertices: Vec<String> = (0..n).filter(|v| layer[*v] == l).map(|v| format!("v{};", v)).collect();
+            writeln!(out, "  {{ rank=same; {} }}", vertices.join(" "))?;
+        }
+        // Invisible edges keep the layers in order even against upsets
+        for l in 1..layers {
+            let first = |l: usize| (0..n).find(|v| layer[*v] == l).expect("layers are not empty");
+            writeln!(out, "  v{} -> v{} [style=invis];", first(l - 1), first(l))?;
+        }
+    }
+
+    for winner in 0..n {
+        for loser in 0..n {
+            if edges[loser][winner] != 0 {
+                writeln!(out, "  v{} -> v{}{};", winner, loser, series_label(graph, winner, loser, ""))?;
+            }
+            if !options.transitive_reduction && winner < loser && graph.is_draw(winner, loser) {
+                writeln!(out, "  v{} -> v{}{};", winner, loser, series_label(graph, winner, loser, "dir=none, style=dashed"))?;
+            }
+        }
+    }
+    writeln!(out, "}}")
+}
+
+/// Edge attributes, labelling the result of a series for graphs with win counts.
+fn series_label(graph: &Graph, a: usize, b: usize, attributes: &str) -> String {
+    let mut attributes: Vec<String> = if attributes.is_empty() { Vec::new() } else { vec![attributes.to_string()] };
+    if graph.win_counts.is_some() {
+        attributes.push(format!("label=\"{}:{}\"", graph.wins(a, b), graph.wins(b, a)));
+    }
+    if attributes.is_empty() { String::new() } else { format!(" [{}]", attributes.join(", ")) }
+}
+
+fn escape(name: &str) -> String {
+    name.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::dot_format::{write_dot, DotLayout, DotOptions};
+    use crate::graph::graph::{DrawSemantics, Graph};
+    use crate::matrix::matrix::Matrix;
+
+    #[test]
+    pub fn kings_are_filled_and_layers_ranked() {
+        // 0 beats 1 and 2, 1 beats 2.
+        let graph = Graph::new(Matrix::with_data(vec![
+            vec![0, 0, 0],
+            vec![1, 0, 0],
+            vec![1, 1, 0],
+        ]));
+        let options = DotOptions { layout: DotLayout::Components, transitive_reduction: true, draws: DrawSemantics::Ignore };
+
+        let mut out = Vec::new();
+        write_dot(&mut out, 0, &graph, &[0], &options).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "\
+digraph \"graph 1\" {
+  node [shape=circle];
+  v0 [label=\"1\", style=filled, fillcolor=gold];
+  v1 [label=\"2\"];
+  v2 [label=\"3\"];
+  { rank=same; v0; }
+  { rank=same; v1; }
+  { rank=same; v2; }
+  v0 -> v1 [style=invis];
+  v1 -> v2 [style=invis];
+  v0 -> v1;
+  v1 -> v2;
+}
+");
+    }
+}
@@ -0,0 +1,182 @@
+use std::io;
+use std::io::{BufRead, Write};
+
+use crate::data_parser::DataParser;
+use crate::format::binary_format::{read_binary, write_binary};
+use crate::format::json_format::{read_json, write_json_graph};
+use crate::graph::graph::Graph;
+
+/// Formats graphs can be read from and written to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphFormat {
+    /// Whitespace-separated matrices, optionally preceded by a row of names marked with `players:`.
+    Matrix,
+    /// One `winner loser` line per game.
+    Matches,
+    /// Comma-separated matrices, optionally preceded by a header row of names marked with `players:`.
+    Csv,
+    /// An array of objects with `labels` and either a `matrix` or a list of `edges`.
+    Json,
+    /// Bit-packed tournaments with a checksum, see `binary_format`.
+    Binary,
+}
+
+impl GraphFormat {
+    pub const NAMES: [&'static str; 5] = ["matrix", "matches", "csv", "json", "binary"];
+
+    pub fn from_name(name: &str) -> Option<GraphFormat> {
+        match name {
+            "matrix" => Some(GraphFormat::Matrix),
+            "matches" => Some(GraphFormat::Matches),
+            "csv" => Some(GraphFormat::Csv),
+            "json" => Some(GraphFormat::Json),
+            "binary" => Some(GraphFormat::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// Graphs of the input in order, parsed as they are consumed.
+/// JSON input is one document and is parsed as a whole on the first call.
+pub fn read_graphs<'a, R: BufRead + 'a>(format: GraphFormat, reader: R) -> Box<dyn Iterator<Item = Result<Graph, io::Error>> + 'a> {
+    match format {
+        GraphFormat::Matrix => Box::new(DataParser::parse_graph_input(reader)),
+        GraphFormat::Matches => Box::new(DataParser::parse_match_input(reader)),
+        GraphFormat::Csv => Box::new(DataParser::parse_csv_input(reader)),
+        GraphFormat::Json => match read_json(reader) {
+            Ok(graphs) => Box::new(graphs.into_iter().map(Ok)),
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        },
+        GraphFormat::Binary => Box::new(read_binary(reader)),
+    }
+}
+
+/// Writes graphs one at a time as they are produced, so no more than one has to be kept in memory.
+/// Text formats separate graphs by blank lines, JSON opens its array before the first graph
+/// and closes it in `finish`.
+/// Headers become comments in the text formats and a `metadata` field in JSON, the binary format drops them.
+pub struct GraphWriter<W: Write> {
+    out: W,
+    format: GraphFormat,
+    graphs: usize,
+}
+
+impl<W: Write> GraphWriter<W> {
+    pub fn new(out: W, format: GraphFormat) -> Self {
+        GraphWriter { out, format, graphs: 0 }
+    }
+
+    /// Writes a graph, preceded by its header if given.
+    pub fn write(&mut self, graph: &Graph, header: Option<&str>) -> Result<(), io::Error> {
+        match self.format {
+            GraphFormat::Json => {
+                write!(self.out, "{}", if self.graphs == 0 { "[" } else { "," })?;
+                write_json_graph(&mut self.out, graph, header)?;
+            }
+            GraphFormat::Binary => write_binary(&mut self.out, graph)?,
+            GraphFormat::Matrix | GraphFormat::Matches | GraphFormat::Csv => {
+                if self.graphs != 0 { writeln!(self.out)? };
+                if let Some(header) = header {
+                    writeln!(self.out, "# {}", header)?;
+                }
+                match self.format {
+                    GraphFormat::Matches => write_matches(&mut self.out, graph)?,
+                    GraphFormat::Csv => write_csv(&mut self.out, graph)?,
+                    _ => write!(self.out, "{}", graph)?,
+                }
+            }
+        }
+        self.graphs += 1;
+        Ok(())
+    }
+
+    /// Completes the output and returns the underlying writer, flushed.
+    pub fn finish(mut self) -> Result<W, io::Error> {
+        if self.format == GraphFormat::Json {
+            if self.graphs == 0 { write!(self.out, "[")? };
+            writeln!(self.out, "]")?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+fn write_csv<W: Write>(out: &mut W, graph: &Graph) -> Result<(), io::Error> {
+    if let Some(labels) = &graph.labels {
+        writeln!(out, "{},{}", DataParser::PLAYERS, labels.join(","))?;
+    }
+    let n = graph.vertices();
+    for row in 0..n {
+        let entries: Vec<String> = (0..n).map(|col| graph.entry(row, col)).collect();
+        writeln!(out, "{}", entries.join(","))?;
+    }
+    Ok(())
+}
+
+fn write_matches<W: Write>(out: &mut W, graph: &Graph) -> Result<(), io::Error> {
+    let n = graph.vertices();
+    for a in 0..n {
+        for b in a + 1..n {
+            if !graph.is_played(a, b) {
+                continue;
+            }
+            let (winner, loser) = if graph.beats(a, b) { (a, b) } else { (b, a) };
+            if graph.is_draw(a, b) || graph.wins(winner, loser) != 1 || graph.wins(loser, winner) != 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("the result of {} against {} is not a single game", graph.vertex_name(a), graph.vertex_name(b))
+                ));
+            }
+            writeln!(out, "{} {}", graph.vertex_name(winner), graph.vertex_name(loser))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::graph_format::{write_csv, write_matches, GraphFormat, GraphWriter};
+    use crate::graph::graph::Graph;
+    use crate::matrix::matrix::Matrix;
+
+    #[test]
+    pub fn csv_has_header_of_names() {
+        // 0 beats 1, 1 beats 2, 2 beats 0
+        let mut graph = Graph::new(Matrix::with_data(vec![
+            vec![0, 0, 1],
+            vec![1, 0, 0],
+            vec![0, 1, 0],
+        ]));
+        graph.labels = Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+
+        let mut out = Vec::new();
+        write_csv(&mut out, &graph).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "players:,a,b,c\n0,1,0\n0,0,1\n1,0,0\n");
+
+        let mut out = Vec::new();
+        write_matches(&mut out, &graph).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "a b\nc a\nb c\n");
+    }
+
+    #[test]
+    pub fn writer_separates_graphs() {
+        let mut graph = Graph::new(Matrix::new(2, 2));
+        graph.set_winner(0, 1);
+
+        let mut text = GraphWriter::new(Vec::new(), GraphFormat::Matrix);
+        text.write(&graph, Some("first")).unwrap();
+        text.write(&graph, None).unwrap();
+        assert_eq!(String::from_utf8(text.finish().unwrap()).unwrap(), "# first\n 0  1 \n 0  0 \n\n 0  1 \n 0  0 \n");
+
+        let mut json = GraphWriter::new(Vec::new(), GraphFormat::Json);
+        json.write(&graph, Some("first")).unwrap();
+        json.write(&graph, None).unwrap();
+        assert_eq!(
+            String::from_utf8(json.finish().unwrap()).unwrap(),
+            "[{\"metadata\":\"first\",\"matrix\":[[0,1],[0,0]]},{\"matrix\":[[0,1],[0,0]]}]\n"
+        );
+
+        let empty = GraphWriter::new(Vec::new(), GraphFormat::Json);
+        assert_eq!(String::from_utf8(empty.finish().unwrap()).unwrap(), "[]\n");
+    }
+}
@@ -0,0 +1,217 @@
+use std::io;
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data_parser::DataParser;
+use crate::graph::graph::Graph;
+
+/// A graph as it appears in JSON input and output, e.g.
+/// `{"labels": ["alice", "bob"], "matrix": [[0, 1], [0, 0]]}` or
+/// `{"labels": ["alice", "bob"], "edges": [["alice", "bob"]]}`.
+#[derive(Serialize, Deserialize)]
+struct JsonGraph {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metadata: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    labels: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    matrix: Option<Vec<Vec<JsonEntry>>>,
+    /// Single games as `[winner, loser]`, pairs without a game are unplayed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    edges: Option<Vec<(JsonVertex, JsonVertex)>>,
+}
+
+/// Matrix entry: the number of wins, or `"-"` and `"="` for unplayed and drawn games.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonEntry {
+    Wins(i32),
+    Mark(String),
+}
+
+/// Vertex of an edge: a name from `labels` or an index starting at 0.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum JsonVertex {
+    Index(usize),
+    Name(String),
+}
+
+/// Without labels, edges name vertices by index and a single large index makes every smaller one a
+/// vertex of the graph. Indices are bounded to keep such input from exhausting memory.
+const MAX_INDEXED_VERTICES: usize = 10_000;
+
+pub fn read_json<R: Read>(reader: R) -> Result<Vec<Graph>, io::Error> {
+    let graphs: Vec<JsonGraph> = serde_json::from_reader(reader)?;
+
+    graphs.into_iter().enumerate().map(|(i, graph)| to_graph(i, graph)).collect()
+}
+
+fn to_graph(index: usize, json: JsonGraph) -> Result<Graph, io::Error> {
+    match (json.matrix, json.edges) {
+        (Some(matrix), None) => {
+            let rows: Vec<Vec<String>> = matrix.into_iter()
+                .map(|row| row.into_iter().map(|entry| match entry {
+                    JsonEntry::Wins(wins) => wins.to_string(),
+                    JsonEntry::Mark(mark) => mark,
+                }).collect())
+                .collect();
+            DataParser::graph_from_rows(json.labels, &rows)
+        }
+        (None, Some(edges)) => {
+            let max_index = edges.iter()
+                .flat_map(|(winner, loser)| [winner, loser])
+                .filter_map(|vertex| if let JsonVertex::Index(i) = vertex { Some(*i) } else { None })
+                .max();
+            // Graphs given only by indices stay unnamed, any name among them keeps all names
+            let by_index = json.labels.is_none()
+                && edges.iter().all(|edge| matches!(edge, (JsonVertex::Index(_), JsonVertex::Index(_))));
+            let players = match json.labels {
+                Some(labels) => labels,
+                None => match max_index {
+                    Some(i) if i >= MAX_INDEXED_VERTICES => return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("graph {} refers to vertex {}, indices are limited to {}", index, i, MAX_INDEXED_VERTICES - 1)
+                    )),
+                    Some(i) => (0..=i).map(|i| i.to_string()).collect(),
+                    None => Vec::new(),
+                },
+            };
+            let mut matches = Vec::with_capacity(edges.len());
+            for (i, (winner, loser)) in edges.into_iter().enumerate() {
+                let location = format!("graph {} edge {}", index, i);
+                let (winner, loser) = (name(winner, &players, &location)?, name(loser, &players, &location)?);
+                matches.push((location, winner, loser));
+            }
+            let mut graph = DataParser::graph_from_matches(players, &matches)?;
+            if by_index {
+                graph.labels = None;
+            }
+            Ok(graph)
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("graph {} needs exactly one of `matrix` and `edges`", index)
+        )),
+    }
+}
+
+/// Name of the player a vertex of an edge refers to, indices count from 0 in `players`.
+fn name(vertex: JsonVertex, players: &[String], location: &str) -> Result<String, io::Error> {
+    match vertex {
+        JsonVertex::Index(i) => players.get(i).cloned().ok_or_else(|| io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: vertex {} is out of range for {} players", location, i, players.len())
+        )),
+        JsonVertex::Name(name) => Ok(name),
+    }
+}
+
+/// Writes a single graph as an object in the matrix form, an element of the array `GraphWriter` writes.
+pub fn write_json_graph<W: Write>(out: &mut W, graph: &Graph, header: Option<&str>) -> Result<(), io::Error> {
+    let n = graph.vertices();
+    let matrix = (0..n)
+        .map(|row| (0..n).map(|col| {
+            let entry = graph.entry(row, col);
+            entry.parse().map(JsonEntry::Wins).unwrap_or(JsonEntry::Mark(entry))
+        }).collect())
+        .collect();
+    let json = JsonGraph {
+        metadata: header.map(String::from),
+        labels: graph.labels.clone(),
+        matrix: Some(matrix),
+        edges: None,
+    };
+
+    serde_json::to_writer(&mut *out, &json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::graph_format::{GraphFormat, GraphWriter};
+    use crate::format::json_format::{to_graph, JsonGraph};
+
+    #[test]
+    pub fn edges_reference_labels_or_indices() {
+        let graphs: Vec<JsonGraph> = serde_json::from_str(r#"[
+            {"labels": ["a", "b", "c"], "edges": [["c", "a"]]},
+            {"edges": [[1, 0], [1, 2]]}
+        ]"#).unwrap();
+        let graphs: Vec<_> = graphs.into_iter().enumerate().map(|(i, g)| to_graph(i, g).unwrap()).collect();
+
+        assert_eq!(graphs[0].vertex_name(2), "c");
+        assert!(graphs[0].beats(2, 0));
+        assert!(!graphs[0].is_played(0, 1));
+        assert_eq!(graphs[1].labels, None);
+        assert!(graphs[1].beats(1, 0) && graphs[1].beats(1, 2));
+    }
+
+    #[test]
+    pub fn names_without_labels_are_kept() {
+        let graphs: Vec<JsonGraph> = serde_json::from_str(r#"[
+            {"edges": [["alice", "bob"], ["bob", "carol"], ["carol", "alice"]]},
+            {"edges": [[0, "bob"]]}
+        ]"#).unwrap();
+        let graphs: Vec<_> = graphs.into_iter().enumerate().map(|(i, g)| to_graph(i, g).unwrap()).collect();
+
+        assert_eq!(graphs[0].labels, Some(vec!["alice".to_string(), "bob".to_string(), "carol".to_string()]));
+        assert!(graphs[0].beats(0, 1) && graphs[0].beats(2, 0));
+        assert_eq!(graphs[1].labels, Some(vec!["0".to_string(), "bob".to_string()]));
+        assert!(graphs[1].beats(0, 1));
+    }
+
+    #[test]
+    pub fn indices_refer_to_labels() {
+        let graphs: Vec<JsonGraph> = serde_json::from_str(r#"[
+            {"labels": ["a", "b", "c"], "edges": [[0, 1], [1, 2], [2, 0]]},
+            {"labels": ["a", "b"], "edges": [[0, 2]]}
+        ]"#).unwrap();
+        let mut graphs = graphs.into_iter().enumerate().map(|(i, g)| to_graph(i, g));
+
+        let cycle = graphs.next().unwrap().unwrap();
+        assert_eq!(3, cycle.vertices());
+        assert!(cycle.is_tournament());
+        assert!(cycle.beats(0, 1) && cycle.beats(1, 2) && cycle.beats(2, 0));
+        assert_eq!("graph 1 edge 0: vertex 2 is out of range for 2 players", graphs.next().unwrap().unwrap_err().to_string());
+    }
+
+    #[test]
+    pub fn written_matrices_read_back() {
+        let graphs: Vec<JsonGraph> = serde_json::from_str(r#"[
+            {"labels": ["a", "b", "c"], "matrix": [[0, 2, "-"], [1, 0, "="], ["-", "=", 0]]}
+        ]"#).unwrap();
+        let graph = to_graph(0, graphs.into_iter().next().unwrap()).unwrap();
+
+        let mut writer = GraphWriter::new(Vec::new(), GraphFormat::Json);
+        writer.write(&graph, None).unwrap();
+
+        assert_eq!(
+            String::from_utf8(writer.finish().unwrap()).unwrap(),
+            "[{\"labels\":[\"a\",\"b\",\"c\"],\"matrix\":[[0,2,\"-\"],[1,0,\"=\"],[\"-\",\"=\",0]]}]\n"
+        );
+    }
+
+    #[test]
+    pub fn malformed_graphs_are_errors() {
+        let graphs: Vec<JsonGraph> = serde_json::from_str(r#"[
+            {"matrix": []},
+            {"matrix": [[0, 1], [0]]},
+            {"edges": [[18446744073709551615, 0]]},
+            {"labels": ["a", "a"], "matrix": [[0, 1], [0, 0]]},
+            {"labels": ["a", "a"], "edges": [["a", "b"]]},
+            {"edges": [["Alice Smith", "Bob"]]}
+        ]"#).unwrap();
+        let errors: Vec<_> = graphs.into_iter().enumerate().map(|(i, g)| to_graph(i, g).unwrap_err().to_string()).collect();
+
+        assert_eq!(errors, [
+            "matrix has no players",
+            "row 2 has 1 entries but the matrix has 2 rows",
+            "graph 2 refers to vertex 18446744073709551615, indices are limited to 9999",
+            "name `a` is given to more than one player",
+            "name `a` is given to more than one player",
+            "name `Alice Smith` is empty, starts with # or contains whitespace, a comma or |",
+        ]);
+    }
+}
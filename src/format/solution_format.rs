@@ -0,0 +1,221 @@
+use std::io;
+use std::io::Write;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Formats of the solutions written by `solve`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolutionFormat {
+    /// Names of the kings separated by spaces, one line per graph.
+    Plain,
+    /// One JSON object per line.
+    Json,
+    Csv,
+    /// A Markdown table.
+    Markdown,
+}
+
+impl SolutionFormat {
+    pub const NAMES: [&'static str; 4] = ["plain", "json", "csv", "markdown"];
+
+    pub fn from_name(name: &str) -> Option<SolutionFormat> {
+        match name {
+            "plain" => Some(SolutionFormat::Plain),
+            "json" => Some(SolutionFormat::Json),
+            "csv" => Some(SolutionFormat::Csv),
+            "markdown" => Some(SolutionFormat::Markdown),
+            _ => None,
+        }
+    }
+}
+
+pub enum Solution {
+    Kings(Vec<String>),
+    /// Kings under every and under some outcome of the unplayed games.
+    Partial { certain: Vec<String>, possible: Vec<String> },
+}
+
+/// Solution of one graph with where it came from and how it was found.
+pub struct SolutionRecord<'a> {
+    pub file: &'a str,
+    /// Position of the graph in its file, starting at 1.
+    pub graph: usize,
+    pub vertices: usize,
+    pub solution: Solution,
+    pub method: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    file: &'a str,
+    graph: usize,
+    vertices: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kings: Option<&'a Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    certain: Option<&'a Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    possible: Option<&'a Vec<String>>,
+    method: &'a str,
+    elapsed_ms: f64,
+}
+
+/// Writes solution records one at a time, with a header before the first one where the format has one.
+pub struct SolutionWriter<W: Write> {
+    out: W,
+    format: SolutionFormat,
+    records: usize,
+}
+
+impl<W: Write> SolutionWriter<W> {
+    pub fn new(out: W, format: SolutionFormat) -> Self {
+        SolutionWriter { out, format, records: 0 }
+    }
+
+    pub fn write(&mut self, record: &SolutionRecord) -> Result<(), io::Error> {
+        let columns: &[&str] = match record.solution {
+            Solution::Kings(_) => &["kings"],
+            Solution::Partial { .. } => &["certain", "possible"],
+        };
+        let header = ["file", "graph", "vertices"].iter()
+            .chain(columns)
+            .chain(&["method", "elapsed_ms"])
+            .map(|column| column.to_string())
+            .collect::<Vec<_>>();
+        let kings = match &record.solution {
+            Solution::Kings(kings) => vec![kings],
+            Solution::Partial { certain, possible } => vec![certain, possible],
+        };
+        let elapsed_ms = format!("{:.3}", record.elapsed.as_secs_f64() * 1000.0);
+
+        match self.format {
+            SolutionFormat::Plain => {
+                let lists: Vec<String> = kings.iter().map(|kings| kings.join(" ")).collect();
+                writeln!(self.out, "{}", lists.join(" | "))?;
+            }
+            SolutionFormat::Json => {
+                let (kings, certain, possible) = match &record.solution {
+                    Solution::Kings(kings) => (Some(kings), None, None),
+                    Solution::Partial { certain, possible } => (None, Some(certain), Some(possible)),
+                };
+                let object = JsonRecord {
+                    file: record.file,
+                    graph: record.graph,
+                    vertices: record.vertices,
+                    kings,
+                    certain,
+                    possible,
+                    method: &record.method,
+                    elapsed_ms: record.elapsed.as_nanos() as f64 / 1e6,
+                };
+                serde_json::to_writer(&mut self.out, &object)?;
+                writeln!(self.out)?;
+            }
+            SolutionFormat::Csv => {
+                if self.records == 0 {
+                    writeln!(self.out, "{}", header.join(","))?;
+                }
+                let fields: Vec<String> = [record.file.to_string(), record.graph.to_string(), record.vertices.to_string()].into_iter()
+                    .chain(kings.iter().map(|kings| kings.join(" ")))
+                    .chain([record.method.clone(), elapsed_ms])
+                    .map(|field| csv_field(&field))
+                    .collect();
+                writeln!(self.out, "{}", fields.join(","))?;
+            }
+            SolutionFormat::Markdown => {
+                if self.records == 0 {
+                    writeln!(self.out, "| {} |", header.join(" | "))?;
+                    writeln!(self.out, "|{}", "---|".repeat(header.len()))?;
+                }
+                let fields: Vec<String> = [record.file.to_string(), record.graph.to_string(), record.vertices.to_string()].into_iter()
+                    .chain(kings.iter().map(|kings| kings.join(", ")))
+                    .chain([record.method.clone(), elapsed_ms])
+                    .map(|field| field.replace('|', "\\|"))
+                    .collect();
+                writeln!(self.out, "| {} |", fields.join(" | "))?;
+            }
+        }
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Flushes the records and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, io::Error> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+/// Quotes a CSV field if it holds a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::format::solution_format::{Solution, SolutionFormat, SolutionRecord, SolutionWriter};
+
+    fn record(graph: usize, kings: &[&str]) -> SolutionRecord<'static> {
+        SolutionRecord {
+            file: "league.txt",
+            graph,
+            vertices: 3,
+            solution: Solution::Kings(kings.iter().map(|k| k.to_string()).collect()),
+            method: "mixed".to_string(),
+            elapsed: Duration::from_micros(1500),
+        }
+    }
+
+    fn written(format: SolutionFormat) -> String {
+        let mut out = Vec::new();
+        let mut writer = SolutionWriter::new(&mut out, format);
+        writer.write(&record(1, &["a", "b"])).unwrap();
+        writer.write(&record(2, &["x,y"])).unwrap();
+        writer.finish().unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    pub fn plain_keeps_king_lists() {
+        assert_eq!("a b\nx,y\n", written(SolutionFormat::Plain));
+    }
+
+    #[test]
+    pub fn csv_has_header_and_quotes() {
+        assert_eq!(
+            "file,graph,vertices,kings,method,elapsed_ms\n\
+            league.txt,1,3,a b,mixed,1.500\n\
+            league.txt,2,3,\"x,y\",mixed,1.500\n",
+            written(SolutionFormat::Csv)
+        );
+    }
+
+    #[test]
+    pub fn json_has_one_object_per_line() {
+        let json = written(SolutionFormat::Json);
+        let first: serde_json::Value = serde_json::from_str(json.lines().next().unwrap()).unwrap();
+
+        assert_eq!(2, json.lines().count());
+        assert_eq!(serde_json::json!(["a", "b"]), first["kings"]);
+        assert_eq!(1, first["graph"]);
+    }
+
+    #[test]
+    pub fn markdown_is_a_table() {
+        assert_eq!(
+            "| file | graph | vertices | kings | method | elapsed_ms |\n\
+            |---|---|---|---|---|---|\n\
+            | league.txt | 1 | 3 | a, b | mixed | 1.500 |\n\
+            | league.txt | 2 | 3 | x,y | mixed | 1.500 |\n",
+            written(SolutionFormat::Markdown)
+        );
+    }
+}
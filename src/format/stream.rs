@@ -0,0 +1,186 @@
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+/// Path standing for the standard input or output.
+pub const STANDARD_STREAM: &str = "-";
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Compression {
+    Gzip,
+    Zstd,
+}
+
+fn compression_of_path(path: &str) -> Option<Compression> {
+    if path.ends_with(".gz") {
+        Some(Compression::Gzip)
+    } else if path.ends_with(".zst") {
+        Some(Compression::Zstd)
+    } else {
+        None
+    }
+}
+
+/// Recognizes compressed data by its first bytes.
+fn compression_of_data(start: &[u8]) -> Option<Compression> {
+    if start.starts_with(GZIP_MAGIC) {
+        Some(Compression::Gzip)
+    } else if start.starts_with(ZSTD_MAGIC) {
+        Some(Compression::Zstd)
+    } else {
+        None
+    }
+}
+
+/// Opens a file for buffered reading, or the standard input for `-`.
+/// Gzip and zstd input is decompressed, detected by extension or magic bytes.
+pub fn open_input(path: &str) -> Result<Box<dyn BufRead>, io::Error> {
+    let mut reader: Box<dyn BufRead> = if path == STANDARD_STREAM {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(File::open(path)?))
+    };
+    let compression = match compression_of_path(path) {
+        Some(compression) => Some(compression),
+        None => compression_of_data(reader.fill_buf()?),
+    };
+    match compression {
+        None => Ok(reader),
+        Some(compression) => decompress(reader, compression),
+    }
+}
+
+/// Buffered output created by `create_output`. Compressed streams end with a trailer,
+/// `finish` writes it and reports whether the whole output was written.
+pub struct Output {
+    stream: OutputStream,
+}
+
+enum OutputStream {
+    Plain(BufWriter<Box<dyn Write>>),
+    #[cfg(feature = "compression")]
+    Gzip(BufWriter<flate2::write::GzEncoder<File>>),
+    #[cfg(feature = "compression")]
+    Zstd(BufWriter<zstd::Encoder<'static, File>>),
+}
+
+impl Output {
+    pub fn finish(self) -> Result<(), io::Error> {
+        match self.stream {
+            OutputStream::Plain(mut out) => out.flush(),
+            #[cfg(feature = "compression")]
+            OutputStream::Gzip(out) => out.into_inner().map_err(io::IntoInnerError::into_error)?.finish()?.flush(),
+            #[cfg(feature = "compression")]
+            OutputStream::Zstd(out) => out.into_inner().map_err(io::IntoInnerError::into_error)?.finish()?.flush(),
+        }
+    }
+
+    fn writer(&mut self) -> &mut dyn Write {
+        match &mut self.stream {
+            OutputStream::Plain(out) => out,
+            #[cfg(feature = "compression")]
+            OutputStream::Gzip(out) => out,
+            #[cfg(feature = "compression")]
+            OutputStream::Zstd(out) => out,
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer().flush()
+    }
+}
+
+/// Creates a file for buffered writing, or writes to the standard output for `-`.
+/// Files ending in `.gz` or `.zst` are compressed, they are complete only after `Output::finish`.
+pub fn create_output(path: &str) -> Result<Output, io::Error> {
+    let stream = if path == STANDARD_STREAM {
+        OutputStream::Plain(BufWriter::new(Box::new(io::stdout().lock())))
+    } else {
+        let file = File::create(path)?;
+        match compression_of_path(path) {
+            None => OutputStream::Plain(BufWriter::new(Box::new(file))),
+            Some(compression) => compress(file, compression)?,
+        }
+    };
+    Ok(Output { stream })
+}
+
+#[cfg(feature = "compression")]
+fn decompress(reader: Box<dyn BufRead>, compression: Compression) -> Result<Box<dyn BufRead>, io::Error> {
+    match compression {
+        Compression::Gzip => Ok(Box::new(BufReader::new(flate2::bufread::MultiGzDecoder::new(reader)))),
+        Compression::Zstd => Ok(Box::new(BufReader::new(zstd::Decoder::with_buffer(reader)?))),
+    }
+}
+
+#[cfg(feature = "compression")]
+fn compress(file: File, compression: Compression) -> Result<OutputStream, io::Error> {
+    match compression {
+        Compression::Gzip => Ok(OutputStream::Gzip(BufWriter::new(flate2::write::GzEncoder::new(file, flate2::Compression::default())))),
+        Compression::Zstd => Ok(OutputStream::Zstd(BufWriter::new(zstd::Encoder::new(file, 0)?))),
+    }
+}
+
+#[cfg(not(feature = "compression"))]
+fn decompress(_reader: Box<dyn BufRead>, compression: Compression) -> Result<Box<dyn BufRead>, io::Error> {
+    Err(unsupported(compression))
+}
+
+#[cfg(not(feature = "compression"))]
+fn compress(_file: File, compression: Compression) -> Result<OutputStream, io::Error> {
+    Err(unsupported(compression))
+}
+
+#[cfg(not(feature = "compression"))]
+fn unsupported(compression: Compression) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{:?} compression needs a build with the `compression` feature", compression)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::stream::{compression_of_data, compression_of_path, Compression};
+
+    #[test]
+    pub fn detects_compression() {
+        assert_eq!(Some(Compression::Gzip), compression_of_path("league.txt.gz"));
+        assert_eq!(Some(Compression::Zstd), compression_of_path("league.bin.zst"));
+        assert_eq!(None, compression_of_path("league.txt"));
+        assert_eq!(Some(Compression::Gzip), compression_of_data(&[0x1f, 0x8b, 0x08]));
+        assert_eq!(Some(Compression::Zstd), compression_of_data(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]));
+        assert_eq!(None, compression_of_data(b"0 1\n"));
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    pub fn compressed_files_read_back() {
+        use std::io::{Read, Write};
+        use crate::format::stream::{create_output, open_input};
+
+        let data = "0 1\n0 0\n".repeat(1000);
+        for extension in ["gz", "zst"] {
+            let path = std::env::temp_dir().join(format!("ping-pong-stream-{}.txt.{}", std::process::id(), extension));
+            let path = path.to_str().unwrap();
+
+            let mut out = create_output(path).unwrap();
+            out.write_all(data.as_bytes()).unwrap();
+            out.finish().unwrap();
+            let mut read = String::new();
+            open_input(path).unwrap().read_to_string(&mut read).unwrap();
+            std::fs::remove_file(path).unwrap();
+
+            assert_eq!(data, read);
+        }
+    }
+}
@@ -1,2 +1,6 @@
+#[allow(clippy::module_inception)]
 pub mod graph;
-pub mod generator;
\ No newline at end of file
+pub mod generator;
+pub mod reachability;
+pub mod score_sequence;
+pub mod strength_model;
\ No newline at end of file
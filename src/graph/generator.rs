@@ -1,15 +1,55 @@
-use rand::Rng;
+use std::fmt;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use crate::graph::graph::Graph;
+use crate::graph::score_sequence::{check_landau, LandauViolation};
 use crate::matrix::matrix::Matrix;
 
+#[derive(Debug, PartialEq)]
+pub enum GeneratorError {
+    InvalidScores(LandauViolation),
+    UnsupportedSize { family: &'static str, size: usize, reason: &'static str },
+    InvalidConnectionSet(String),
+    TooManyUpsets { upsets: usize, games: usize },
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratorError::InvalidScores(violation) =>
+                write!(f, "Not a tournament score sequence: {}", violation),
+            GeneratorError::UnsupportedSize { family, size, reason } =>
+                write!(f, "Cannot generate {} tournament of size {}: {}", family, size, reason),
+            GeneratorError::InvalidConnectionSet(reason) =>
+                write!(f, "Invalid connection set: {}", reason),
+            GeneratorError::TooManyUpsets { upsets, games } =>
+                write!(f, "Cannot reverse {} games in a tournament with {} games", upsets, games),
+        }
+    }
+}
+
+impl From<LandauViolation> for GeneratorError {
+    fn from(violation: LandauViolation) -> Self {
+        GeneratorError::InvalidScores(violation)
+    }
+}
+
 
 pub fn inverse_cantor(z : i32) -> (i32,i32){
     let n = ((-1.0+((1+8*z) as f64).sqrt())/2.0).floor() as i32;
     let y:i32 = z - (n*(n+1))/2;
     (n - y, y)
 }
-pub fn generate_ping_pong(size: i32, p: f64) -> Graph {
-    let mut rng = rand::thread_rng();
+/// Random number generator for the `index`-th graph generated from `seed`.
+/// Each graph gets its own ChaCha stream, so it can be reproduced on any platform
+/// without generating the graphs before it.
+pub fn seeded_rng(seed: u64, index: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(index);
+    rng
+}
+
+pub fn generate_ping_pong(size: i32, p: f64, rng: &mut impl Rng) -> Graph {
     let mut m = Matrix::new(size as usize, size as usize);
     let n = (size-1)*size/2;
     (0..n)
@@ -18,4 +58,353 @@ pub fn generate_ping_pong(size: i32, p: f64) -> Graph {
         .map(|(x,y)| { if rng.gen_bool(p) { (y,x) } else { (x,y) } })
         .for_each(|(x,y)| { m[x as usize][y as usize] = 1 });
     Graph::new(m)
-}
\ No newline at end of file
+}
+
+/// Maps `z` in `0..size*(size-1)/2` to a distinct pair `(x, y)` with `x > y`.
+fn pair_of_index(size: usize, z: usize) -> (usize, usize) {
+    let (x, y) = inverse_cantor(z as i32);
+    (size - 1 - x as usize, y as usize)
+}
+
+/// Vertex `i` beats `j` if and only if `i < j`. Vertex 0 is the only king.
+pub fn generate_transitive(size: usize) -> Graph {
+    let mut graph = Graph::new(Matrix::new(size, size));
+    for loser in 0..size {
+        for winner in 0..loser {
+            graph.set_winner(winner, loser);
+        }
+    }
+    graph
+}
+
+/// Circulant tournament in which vertex `i` beats `j` if and only if `(j - i) mod size`
+/// belongs to the connection set. The set must contain exactly one of `d` and `size - d`
+/// for every `d` in `1..size`, so `size` has to be odd.
+pub fn generate_rotational(size: usize, connection_set: &[usize]) -> Result<Graph, GeneratorError> {
+    let mut in_set = vec![false; size];
+    for d in connection_set {
+        if *d == 0 || *d >= size {
+            return Err(GeneratorError::InvalidConnectionSet(format!("{} is not in 1..{}", d, size)));
+        }
+        in_set[*d] = true;
+    }
+    for d in 1..size {
+        if in_set[d] == in_set[size - d] {
+            return Err(GeneratorError::InvalidConnectionSet(
+                format!("exactly one of {} and {} must belong to the set", d, size - d)
+            ));
+        }
+    }
+
+    let mut graph = Graph::new(Matrix::new(size, size));
+    for i in 0..size {
+        for j in 0..size {
+            if i != j && in_set[(j + size - i) % size] {
+                graph.set_winner(i, j);
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Every vertex wins exactly `(size - 1) / 2` games, which makes every vertex a king.
+pub fn generate_regular(size: usize) -> Result<Graph, GeneratorError> {
+    if size.is_multiple_of(2) {
+        return Err(GeneratorError::UnsupportedSize { family: "regular", size, reason: "size must be odd" });
+    }
+    let connection_set: Vec<usize> = (1..=size / 2).collect();
+    generate_rotational(size, &connection_set)
+}
+
+/// Quadratic-residue tournament: `i` beats `j` if and only if `j - i` is a nonzero square modulo `size`.
+/// Requires a prime `size` congruent to 3 modulo 4, so that exactly one of `d` and `-d` is a square.
+pub fn generate_paley(size: usize) -> Result<Graph, GeneratorError> {
+    if size % 4 != 3 || !is_prime(size) {
+        return Err(GeneratorError::UnsupportedSize {
+            family: "paley", size, reason: "size must be a prime congruent to 3 modulo 4"
+        });
+    }
+    let mut residues: Vec<usize> = (1..size).map(|x| x * x % size).collect();
+    residues.sort_unstable();
+    residues.dedup();
+    generate_rotational(size, &residues)
+}
+
+/// Regular tournament in which every pair of vertices is beaten by the same number of common vertices.
+///
+/// These are the cores of skew Hadamard matrices of order `size + 1`, with one player for every row
+/// but the first and `i` beating `j` if the entry is 1 once the first row is all ones. The matrix is
+/// found by doubling the one of a Paley tournament, or the matrix of order 1, so `size + 1` has to be
+/// a power of two times one more than a prime congruent to 3 modulo 4, e.g. 7, 15 or 31.
+pub fn generate_doubly_regular(size: usize) -> Result<Graph, GeneratorError> {
+    let mut order = size + 1;
+    let mut doublings = 0;
+    while order != 1 && !(order.is_multiple_of(4) && is_prime(order - 1)) {
+        if !order.is_multiple_of(2) {
+            return Err(GeneratorError::UnsupportedSize {
+                family: "doubly-regular", size,
+                reason: "size + 1 must be a power of two times one more than a prime congruent to 3 modulo 4"
+            });
+        }
+        order /= 2;
+        doublings += 1;
+    }
+
+    // Skew Hadamard matrix of the Paley tournament: ones in the first row and on the diagonal,
+    // -1 below the first row and the tournament's results elsewhere
+    let base = if order == 1 { Graph::new(Matrix::new(0, 0)) } else { generate_paley(order - 1)? };
+    let mut hadamard: Vec<Vec<i8>> = (0..order)
+        .map(|i| (0..order).map(|j| match (i, j) {
+            (0, _) => 1,
+            (_, 0) => -1,
+            _ if i == j || base.beats(i - 1, j - 1) => 1,
+            _ => -1,
+        }).collect())
+        .collect();
+    // [[H, H], [-Hᵀ, Hᵀ]] is again a skew Hadamard matrix
+    for _ in 0..doublings {
+        let n = hadamard.len();
+        hadamard = (0..2 * n)
+            .map(|i| (0..2 * n).map(|j| match (i < n, j < n) {
+                (true, _) => hadamard[i][j % n],
+                (false, true) => -hadamard[j][i - n],
+                (false, false) => hadamard[j - n][i - n],
+            }).collect())
+            .collect();
+    }
+
+    // Negating rows and columns together keeps the matrix skew and makes its first row all ones
+    let signs = hadamard[0].clone();
+    let mut graph = Graph::new(Matrix::new(size, size));
+    for i in 1..=size {
+        for j in 1..=size {
+            if i != j && signs[i] * hadamard[i][j] * signs[j] == 1 {
+                graph.set_winner(i - 1, j - 1);
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Transitive tournament with `upsets` distinct games chosen at random and reversed.
+pub fn generate_upset(size: usize, upsets: usize, rng: &mut impl Rng) -> Result<Graph, GeneratorError> {
+    let games = size * size.saturating_sub(1) / 2;
+    if upsets > games {
+        return Err(GeneratorError::TooManyUpsets { upsets, games });
+    }
+
+    let mut graph = generate_transitive(size);
+    for z in rand::seq::index::sample(rng, games, upsets) {
+        let (x, y) = pair_of_index(size, z);
+        graph.set_winner(x, y);
+    }
+    Ok(graph)
+}
+
+fn is_prime(n: usize) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+}
+
+/// Builds a tournament in which vertex `i` wins exactly `scores[i]` games.
+///
+/// Vertices are removed one by one, always taking the one with the highest remaining score.
+/// It loses to the vertices with the highest remaining scores and beats everyone else,
+/// which keeps the residual sequence valid as long as the input satisfies Landau's conditions.
+pub fn generate_from_scores(scores: &[i32]) -> Result<Graph, GeneratorError> {
+    check_landau(scores)?;
+
+    let size = scores.len();
+    let mut graph = Graph::new(Matrix::new(size, size));
+    let mut remaining_scores = scores.to_vec();
+    let mut remaining: Vec<usize> = (0..size).collect();
+
+    while !remaining.is_empty() {
+        remaining.sort_by_key(|v| (-remaining_scores[*v], *v));
+        let v = remaining.remove(0);
+        let losses = remaining.len() - remaining_scores[v] as usize;
+
+        for (i, u) in remaining.iter().enumerate() {
+            if i < losses {
+                remaining_scores[*u] -= 1;
+                graph.set_winner(*u, v);
+            } else {
+                graph.set_winner(v, *u);
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Randomly walks between tournaments with the same score sequence.
+/// Each attempt picks three vertices and reverses them if they form a directed 3-cycle.
+pub fn reverse_random_3_cycles(graph: &mut Graph, attempts: usize, rng: &mut impl Rng) {
+    let size = graph.vertices() as u32;
+    if size < 3 { return; }
+
+    for _ in 0..attempts {
+        // Sampling u32 instead of usize keeps the sequence identical on 32 and 64-bit platforms.
+        let a = rng.gen_range(0..size) as usize;
+        let b = rng.gen_range(0..size) as usize;
+        let c = rng.gen_range(0..size) as usize;
+        if a == b || b == c || a == c { continue; }
+
+        if graph.beats(a, b) && graph.beats(b, c) && graph.beats(c, a) {
+            graph.set_winner(b, a);
+            graph.set_winner(c, b);
+            graph.set_winner(a, c);
+        } else if graph.beats(b, a) && graph.beats(c, b) && graph.beats(a, c) {
+            graph.set_winner(a, b);
+            graph.set_winner(b, c);
+            graph.set_winner(c, a);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use crate::graph::generator::{
+        generate_doubly_regular,
+        generate_from_scores,
+        generate_paley,
+        generate_regular,
+        generate_rotational,
+        generate_transitive,
+        generate_ping_pong,
+        generate_upset,
+        reverse_random_3_cycles,
+        seeded_rng
+    };
+    use crate::matrix::naive_mul::naive_mul;
+    use crate::ping_pong::ping_pong_solver::PingPongSolver;
+
+    #[test]
+    pub fn realises_score_sequence() {
+        let scores = vec![3, 2, 2, 1, 2];
+
+        let graph = generate_from_scores(&scores).expect("valid sequence");
+
+        assert_eq!(scores, graph.score_sequence());
+    }
+
+    #[test]
+    pub fn realises_transitive_score_sequence() {
+        let scores: Vec<i32> = (0..10).collect();
+
+        let graph = generate_from_scores(&scores).expect("valid sequence");
+
+        assert_eq!(scores, graph.score_sequence());
+    }
+
+    #[test]
+    pub fn rejects_invalid_score_sequence() {
+        assert!(generate_from_scores(&[2, 2, 0]).is_err());
+    }
+
+    #[test]
+    pub fn cycle_reversals_keep_scores() {
+        let scores = vec![4, 4, 4, 4, 4, 4, 4, 4, 4];
+        let mut graph = generate_from_scores(&scores).expect("valid sequence");
+
+        reverse_random_3_cycles(&mut graph, 1000, &mut rand::thread_rng());
+
+        assert_eq!(scores, graph.score_sequence());
+        for v in 0..graph.vertices() {
+            for u in 0..graph.vertices() {
+                if u != v { assert_ne!(graph.beats(u, v), graph.beats(v, u)); }
+            }
+        }
+    }
+
+    #[test]
+    pub fn transitive_has_one_king() {
+        let graph = generate_transitive(7);
+
+        assert!(graph.is_tournament());
+        assert_eq!(vec![6, 5, 4, 3, 2, 1, 0], graph.score_sequence());
+        assert_eq!(vec![0], PingPongSolver::new(naive_mul).solve(&graph));
+    }
+
+    #[test]
+    pub fn regular_families_make_everyone_king() {
+        let graphs = vec![
+            generate_regular(9).expect("odd size"),
+            generate_paley(11).expect("prime 3 mod 4"),
+            generate_doubly_regular(7).expect("prime 3 mod 4"),
+        ];
+
+        for graph in graphs {
+            let n = graph.vertices();
+            assert!(graph.is_tournament());
+            assert!(graph.score_sequence().iter().all(|s| *s as usize == (n - 1) / 2));
+            assert_eq!((0..n as i32).collect::<Vec<_>>(), PingPongSolver::new(naive_mul).solve(&graph));
+        }
+    }
+
+    #[test]
+    pub fn doubly_regular_pairs_share_dominators() {
+        // 15, 31 and 47 are not prime, they come from doubling Hadamard matrices of order 8, 1 and 12.
+        for size in [1, 3, 7, 11, 15, 23, 31, 47] {
+            let graph = generate_doubly_regular(size).expect("skew Hadamard matrix of order size + 1");
+
+            assert!(graph.is_tournament());
+            assert!(graph.score_sequence().iter().all(|s| *s as usize == (size - 1) / 2));
+            for u in 0..size {
+                for v in 0..u {
+                    let common = (0..size).filter(|w| graph.beats(*w, u) && graph.beats(*w, v)).count();
+                    assert_eq!((size - 3) / 4, common, "size {}, vertices {} and {}", size, u, v);
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn paley_beats_quadratic_residues() {
+        let graph = generate_paley(7).expect("prime 3 mod 4");
+
+        // Nonzero squares modulo 7 are 1, 2 and 4.
+        assert!(graph.beats(0, 1) && graph.beats(0, 2) && graph.beats(0, 4));
+        assert!(graph.beats(3, 0) && graph.beats(5, 0) && graph.beats(6, 0));
+    }
+
+    #[test]
+    pub fn rejects_unsupported_sizes() {
+        assert!(generate_regular(8).is_err());
+        assert!(generate_paley(13).is_err());
+        assert!(generate_paley(15).is_err());
+        assert!(generate_doubly_regular(9).is_err());
+        assert!(generate_doubly_regular(13).is_err());
+        assert!(generate_rotational(5, &[1, 4]).is_err());
+        assert!(generate_rotational(5, &[1]).is_err());
+    }
+
+    #[test]
+    pub fn upsets_reverse_distinct_games() {
+        let graph = generate_upset(10, 45, &mut rand::thread_rng()).expect("all games can be reversed");
+
+        assert!(graph.is_tournament());
+        assert_eq!((0..10).collect::<Vec<_>>(), graph.score_sequence());
+        assert!(generate_upset(10, 46, &mut rand::thread_rng()).is_err());
+    }
+
+    #[test]
+    pub fn same_seed_generates_same_graphs() {
+        let first = generate_ping_pong(20, 0.5, &mut seeded_rng(42, 3));
+        let second = generate_ping_pong(20, 0.5, &mut seeded_rng(42, 3));
+        let other_stream = generate_ping_pong(20, 0.5, &mut seeded_rng(42, 4));
+
+        assert_eq!(first.adj_matrix, second.adj_matrix);
+        assert_ne!(first.adj_matrix, other_stream.adj_matrix);
+    }
+
+    proptest! {
+        #[test]
+        fn random_graphs_are_tournaments(size in 0..40i32, p in 0.0..=1.0f64, seed: u64) {
+            let graph = generate_ping_pong(size, p, &mut seeded_rng(seed, 0));
+
+            prop_assert_eq!(size as usize, graph.vertices());
+            prop_assert!(graph.is_tournament());
+        }
+    }
+}
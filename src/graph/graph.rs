@@ -1,13 +1,214 @@
 use std::fmt;
+use crate::data_parser::DataParser;
 use crate::matrix::matrix::Matrix;
 
+/// Outcome of the game between two players, from the point of view of the first one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PairResult {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// How drawn games count towards beating an opponent and towards the score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrawSemantics {
+    /// A draw is neither a win nor a loss and adds nothing to the score.
+    Ignore,
+    /// Both players beat each other and score a full point.
+    Mutual,
+    /// Both players score half a point, but nobody beats the other one.
+    Half,
+}
+
 pub struct Graph {
-    pub adj_matrix : Matrix<i32>
+    pub adj_matrix : Matrix<i32>,
+    /// Sorted pairs `(i, j)` with `i < j` whose game has not been played yet.
+    /// Both directions are 0 in the adjacency matrix for such pairs.
+    pub unplayed: Vec<(usize, usize)>,
+    /// Sorted pairs `(i, j)` with `i < j` whose game, or series of games, ended in a draw.
+    /// Both directions are 0 in the adjacency matrix for such pairs.
+    pub draws: Vec<(usize, usize)>,
+    /// Number of games each player won against each other, laid out like the adjacency matrix.
+    /// Not present when every pair played a single game, the adjacency matrix holds the counts then.
+    pub win_counts: Option<Matrix<i32>>,
+    /// Names of the players, one per vertex.
+    pub labels: Option<Vec<String>>
 }
 
 impl Graph {
     pub fn new(adj_matrix : Matrix<i32>) -> Self {
-        Graph{ adj_matrix }
+        Graph{ adj_matrix, unplayed: Vec::new(), draws: Vec::new(), win_counts: None, labels: None }
+    }
+
+    pub fn with_unplayed(adj_matrix : Matrix<i32>, unplayed: Vec<(usize, usize)>) -> Self {
+        let mut unplayed: Vec<_> = unplayed.into_iter().map(|(a, b)| (a.min(b), a.max(b))).collect();
+        unplayed.sort_unstable();
+        unplayed.dedup();
+        Graph{ adj_matrix, unplayed, draws: Vec::new(), win_counts: None, labels: None }
+    }
+
+    /// A player beats another one if they won more games against them.
+    /// Equal nonzero counts make a drawn series, equal zero counts make no edge.
+    pub fn from_win_counts(win_counts: Matrix<i32>, unplayed: Vec<(usize, usize)>) -> Self {
+        let n = win_counts.cols();
+        let mut adj_matrix = Matrix::new(n, n);
+        for loser in 0..n {
+            for winner in 0..n {
+                if win_counts[loser][winner] > win_counts[winner][loser] {
+                    adj_matrix[loser][winner] = 1;
+                }
+            }
+        }
+        let mut graph = Graph::with_unplayed(adj_matrix, unplayed);
+        for a in 0..n {
+            for b in 0..a {
+                if win_counts[a][b] > 0 && win_counts[a][b] == win_counts[b][a] {
+                    graph.set_draw(a, b);
+                }
+            }
+        }
+        graph.win_counts = Some(win_counts);
+        graph
+    }
+
+    pub fn vertices(&self) -> usize {
+        self.adj_matrix.cols()
+    }
+
+    /// Label of the vertex, or its 1-based index if the graph has no labels.
+    pub fn vertex_name(&self, v: usize) -> String {
+        match &self.labels {
+            Some(labels) => labels[v].clone(),
+            None => (v + 1).to_string(),
+        }
+    }
+
+    pub fn beats(&self, winner: usize, loser: usize) -> bool {
+        self.adj_matrix[loser][winner] != 0
+    }
+
+    /// Number of games `winner` won against `loser`.
+    pub fn wins(&self, winner: usize, loser: usize) -> i32 {
+        match &self.win_counts {
+            Some(counts) => counts[loser][winner],
+            None => self.adj_matrix[loser][winner],
+        }
+    }
+
+    /// By how many games `winner` won the series against `loser`, negative if they lost it.
+    pub fn margin(&self, winner: usize, loser: usize) -> i32 {
+        self.wins(winner, loser) - self.wins(loser, winner)
+    }
+
+    /// Adjacency matrix keeping only the wins by a margin of at least `threshold` games.
+    pub fn dominance_matrix(&self, threshold: i32) -> Matrix<i32> {
+        let n = self.vertices();
+        let mut m = Matrix::new(n, n);
+        for loser in 0..n {
+            for winner in 0..n {
+                if winner != loser && self.margin(winner, loser) >= threshold.max(1) {
+                    m[loser][winner] = 1;
+                }
+            }
+        }
+        m
+    }
+
+    pub fn is_partial(&self) -> bool {
+        !self.unplayed.is_empty()
+    }
+
+    pub fn is_played(&self, a: usize, b: usize) -> bool {
+        self.unplayed.binary_search(&(a.min(b), a.max(b))).is_err()
+    }
+
+    pub fn is_draw(&self, a: usize, b: usize) -> bool {
+        self.draws.binary_search(&(a.min(b), a.max(b))).is_ok()
+    }
+
+    pub fn result(&self, player: usize, opponent: usize) -> Option<PairResult> {
+        if !self.is_played(player, opponent) {
+            None
+        } else if self.is_draw(player, opponent) {
+            Some(PairResult::Draw)
+        } else if self.beats(player, opponent) {
+            Some(PairResult::Win)
+        } else if self.beats(opponent, player) {
+            Some(PairResult::Loss)
+        } else {
+            None
+        }
+    }
+
+    /// Entry of the input matrix for `row` against `col`: the wins of `row`,
+    /// `-` for an unplayed game or `=` for a draw without wins on either side.
+    pub fn entry(&self, row: usize, col: usize) -> String {
+        if row != col && !self.is_played(row, col) {
+            "-".to_string()
+        } else if self.is_draw(row, col) && self.wins(row, col) == 0 && self.wins(col, row) == 0 {
+            "=".to_string()
+        } else {
+            self.wins(row, col).to_string()
+        }
+    }
+
+    /// Every pair of distinct vertices played exactly one game and nobody beat themselves.
+    pub fn is_tournament(&self) -> bool {
+        let n = self.vertices();
+        (0..n).all(|v| !self.beats(v, v) && (0..v).all(|u| self.beats(u, v) != self.beats(v, u)))
+    }
+
+    /// Orients the game between two vertices, overriding its previous result.
+    pub fn set_winner(&mut self, winner: usize, loser: usize) {
+        self.adj_matrix[loser][winner] = 1;
+        self.adj_matrix[winner][loser] = 0;
+        if let Some(counts) = &mut self.win_counts {
+            counts[loser][winner] = 1;
+            counts[winner][loser] = 0;
+        }
+        let pair = (winner.min(loser), winner.max(loser));
+        self.draws.retain(|p| *p != pair);
+    }
+
+    /// Records a draw between two vertices, overriding the previous result of their game.
+    pub fn set_draw(&mut self, a: usize, b: usize) {
+        self.adj_matrix[a][b] = 0;
+        self.adj_matrix[b][a] = 0;
+        let pair = (a.min(b), a.max(b));
+        if let Err(i) = self.draws.binary_search(&pair) {
+            self.draws.insert(i, pair);
+        }
+    }
+
+    /// Points of every vertex: one for each opponent beaten plus the share of each draw.
+    pub fn scores(&self, semantics: DrawSemantics) -> Vec<f64> {
+        let draw_points = match semantics {
+            DrawSemantics::Ignore => 0.0,
+            DrawSemantics::Mutual => 1.0,
+            DrawSemantics::Half => 0.5,
+        };
+        let mut scores: Vec<f64> = self.score_sequence().into_iter().map(|s| s as f64).collect();
+        for (a, b) in &self.draws {
+            scores[*a] += draw_points;
+            scores[*b] += draw_points;
+        }
+        scores
+    }
+
+    /// Out-degree of every vertex, i.e. the number of games each player has won.
+    pub fn score_sequence(&self) -> Vec<i32> {
+        (0..self.vertices())
+            .map(|v| (0..self.vertices()).filter(|u| self.beats(v, *u)).count() as i32)
+            .collect()
+    }
+
+    /// Vertex with the highest score, lowest index on ties.
+    /// By Landau's theorem such a vertex is always a king in a tournament.
+    pub fn max_score_vertex(&self, semantics: DrawSemantics) -> Option<usize> {
+        let scores = self.scores(semantics);
+        let max = scores.iter().copied().reduce(f64::max)?;
+        scores.iter().position(|s| *s == max)
     }
 }
 
@@ -21,7 +222,74 @@ impl fmt::Debug for Graph {
 
 impl fmt::Display for Graph {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:}", &self.adj_matrix)?;
+        let m = self.win_counts.as_ref().unwrap_or(&self.adj_matrix);
+        if let Some(labels) = &self.labels {
+            writeln!(f, "{} {}", DataParser::PLAYERS, labels.join(" "))?;
+        }
+        if !self.is_partial() && self.draws.is_empty() {
+            return write!(f, "{:}", m);
+        }
+        for row in 0..m.rows() {
+            for col in 0..m.cols() {
+                write!(f, "{:>2} ", self.entry(row, col))?;
+            }
+            writeln!(f)?;
+        }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::graph::{DrawSemantics, Graph, PairResult};
+    use crate::matrix::matrix::Matrix;
+
+    #[test]
+    pub fn orientation_follows_win_counts() {
+        // Columns hold losses: 0 won 3:1 against 1, 1 and 2 split 2:2, 2 won 1:0 against 0.
+        let counts = Matrix::with_data(vec![
+            vec![0, 1, 1],
+            vec![3, 0, 2],
+            vec![0, 2, 0],
+        ]);
+
+        let graph = Graph::from_win_counts(counts, Vec::new());
+
+        assert!(graph.beats(0, 1) && !graph.beats(1, 0));
+        assert!(!graph.beats(1, 2) && !graph.beats(2, 1));
+        assert_eq!(Some(PairResult::Draw), graph.result(1, 2));
+        assert!(graph.beats(2, 0));
+        assert_eq!(2, graph.margin(0, 1));
+        assert_eq!(-1, graph.margin(0, 2));
+    }
+
+    #[test]
+    pub fn dominance_matrix_drops_narrow_wins() {
+        let counts = Matrix::with_data(vec![
+            vec![0, 1, 1],
+            vec![3, 0, 2],
+            vec![0, 2, 0],
+        ]);
+        let graph = Graph::from_win_counts(counts, Vec::new());
+
+        let dominance = graph.dominance_matrix(2);
+
+        assert_eq!(1, dominance[1][0]);
+        assert_eq!(0, dominance[0][2]);
+        assert_eq!(graph.adj_matrix, graph.dominance_matrix(1));
+    }
+
+    #[test]
+    pub fn draws_score_according_to_semantics() {
+        let mut graph = Graph::new(Matrix::new(3, 3));
+        graph.set_winner(0, 1);
+        graph.set_winner(2, 0);
+        graph.set_draw(1, 2);
+
+        assert_eq!(Some(PairResult::Win), graph.result(0, 1));
+        assert_eq!(Some(PairResult::Loss), graph.result(0, 2));
+        assert_eq!(vec![1.0, 0.0, 1.0], graph.scores(DrawSemantics::Ignore));
+        assert_eq!(vec![1.0, 1.0, 2.0], graph.scores(DrawSemantics::Mutual));
+        assert_eq!(vec![1.0, 0.5, 1.5], graph.scores(DrawSemantics::Half));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::matrix::matrix::Matrix;
+
+/// Whether the adjacency matrix has an edge from `from` to `to`.
+fn edge(adj: &Matrix<i32>, from: usize, to: usize) -> bool {
+    adj[to][from] != 0
+}
+
+/// Vertices reachable from `from`, ignoring the edge `skip` if given.
+fn reachable(adj: &Matrix<i32>, from: usize, skip: Option<(usize, usize)>) -> Vec<bool> {
+    let n = adj.cols();
+    let mut seen = vec![false; n];
+    let mut stack = vec![from];
+    seen[from] = true;
+    while let Some(v) = stack.pop() {
+        for (w, seen) in seen.iter_mut().enumerate() {
+            if !*seen && edge(adj, v, w) && skip != Some((v, w)) {
+                *seen = true;
+                stack.push(w);
+            }
+        }
+    }
+    seen
+}
+
+/// Strongly connected component of every vertex. Components are numbered in topological order,
+/// so no vertex has an edge to a component with a smaller number.
+pub fn strong_components(adj: &Matrix<i32>) -> Vec<usize> {
+    let n = adj.cols();
+    let reach: Vec<Vec<bool>> = (0..n).map(|v| reachable(adj, v, None)).collect();
+
+    // A vertex precedes another component if it reaches it without being reached back,
+    // so the number of vertices reaching `v` one-way orders the components.
+    let reached_by_others = |v: usize| (0..n).filter(|u| reach[*u][v] && !reach[v][*u]).count();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|v| reached_by_others(*v));
+
+    let mut components = vec![usize::MAX; n];
+    let mut count = 0;
+    for v in order {
+        if components[v] != usize::MAX {
+            continue;
+        }
+        for u in 0..n {
+            if reach[v][u] && reach[u][v] {
+                components[u] = count;
+            }
+        }
+        count += 1;
+    }
+    components
+}
+
+/// Edges `(from, to)` of a breadth-first tree of `root` within its strongly connected component,
+/// directed away from the root, or towards it with `inward`.
+fn search_tree(adj: &Matrix<i32>, components: &[usize], root: usize, inward: bool) -> Vec<(usize, usize)> {
+    let n = adj.cols();
+    let mut seen = vec![false; n];
+    let mut queue = std::collections::VecDeque::from([root]);
+    let mut tree = Vec::new();
+    seen[root] = true;
+    while let Some(v) = queue.pop_front() {
+        for w in 0..n {
+            let (from, to) = if inward { (w, v) } else { (v, w) };
+            if !seen[w] && components[w] == components[root] && edge(adj, from, to) {
+                seen[w] = true;
+                tree.push((from, to));
+                queue.push_back(w);
+            }
+        }
+    }
+    tree
+}
+
+/// A minimal subgraph with the same reachability: dropping any of its edges makes some vertex
+/// lose another one. For acyclic graphs this is the unique transitive reduction, cyclic ones can
+/// have smaller subgraphs with the same reachability.
+///
+/// Every strongly connected component keeps the spanning trees out of and into one of its
+/// vertices, less the edges the others make redundant. Components are joined by one edge for each
+/// edge of the reduction of their acyclic condensation.
+pub fn transitive_reduction(adj: &Matrix<i32>) -> Matrix<i32> {
+    let n = adj.cols();
+    let components = strong_components(adj);
+    let count = components.iter().max().map_or(0, |c| c + 1);
+    let mut reduced = Matrix::new(n, n);
+
+    for component in 0..count {
+        let members: Vec<usize> = (0..n).filter(|v| components[*v] == component).collect();
+        let root = members[0];
+        let trees = [search_tree(adj, &components, root, false), search_tree(adj, &components, root, true)];
+        for (from, to) in trees.concat() {
+            reduced[to][from] = 1;
+        }
+        for &from in &members {
+            for &to in &members {
+                if from != to && edge(&reduced, from, to) && reachable(&reduced, from, Some((from, to)))[to] {
+                    reduced[to][from] = 0;
+                }
+            }
+        }
+    }
+
+    // One edge between each pair of components with an edge of the condensation,
+    // which only goes from smaller to larger component numbers.
+    let mut joins: Vec<Vec<Option<(usize, usize)>>> = vec![vec![None; count]; count];
+    for from in 0..n {
+        for to in 0..n {
+            if components[from] != components[to] && edge(adj, from, to) {
+                joins[components[from]][components[to]].get_or_insert((from, to));
+            }
+        }
+    }
+    let mut reaches = vec![vec![false; count]; count];
+    for (c, row) in joins.iter().enumerate().rev() {
+        let (before, after) = reaches.split_at_mut(c + 1);
+        before[c][c] = true;
+        for (d, join) in row.iter().enumerate().skip(c + 1) {
+            if join.is_some() {
+                before[c].iter_mut().zip(&after[d - c - 1]).for_each(|(reach, via)| *reach |= *via);
+            }
+        }
+    }
+    for (c, row) in joins.iter().enumerate() {
+        for (d, join) in row.iter().enumerate().skip(c + 1) {
+            let Some((from, to)) = *join else { continue };
+            let indirect = (c + 1..d).any(|e| row[e].is_some() && reaches[e][d]);
+            if !indirect {
+                reduced[to][from] = 1;
+            }
+        }
+    }
+    reduced
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::generator::{generate_ping_pong, seeded_rng};
+    use crate::graph::reachability::{reachable, strong_components, transitive_reduction};
+    use crate::matrix::matrix::Matrix;
+
+    #[test]
+    pub fn components_in_topological_order() {
+        // 3 beats the 3-cycle 0 -> 1 -> 2 -> 0, which beats 4.
+        let mut adj = Matrix::new(5, 5);
+        for (winner, loser) in [(0, 1), (1, 2), (2, 0), (3, 0), (3, 1), (3, 2), (0, 4), (1, 4), (2, 4), (3, 4)] {
+            adj[loser][winner] = 1;
+        }
+
+        assert_eq!(vec![1, 1, 1, 0, 2], strong_components(&adj));
+    }
+
+    #[test]
+    pub fn reduction_of_transitive_tournament_is_a_path() {
+        let mut adj = Matrix::new(4, 4);
+        for winner in 0..4 {
+            for loser in winner + 1..4 {
+                adj[loser][winner] = 1;
+            }
+        }
+
+        let reduced = transitive_reduction(&adj);
+
+        let edges: Vec<_> = (0..4)
+            .flat_map(|from| (0..4).map(move |to| (from, to)))
+            .filter(|(from, to)| reduced[*to][*from] != 0)
+            .collect();
+        assert_eq!(vec![(0, 1), (1, 2), (2, 3)], edges);
+    }
+
+    #[test]
+    pub fn reduction_keeps_reachability_and_is_minimal() {
+        let graph = generate_ping_pong(12, 0.3, &mut seeded_rng(3, 0));
+        let reachability = |adj: &Matrix<i32>| (0..12).map(|v| reachable(adj, v, None)).collect::<Vec<_>>();
+
+        let components = strong_components(&graph.adj_matrix);
+        assert!(components.iter().any(|c| components.iter().filter(|d| *d == c).count() > 2));
+
+        let reduced = transitive_reduction(&graph.adj_matrix);
+
+        assert_eq!(reachability(&graph.adj_matrix), reachability(&reduced));
+        for from in 0..12 {
+            for to in 0..12 {
+                if reduced[to][from] != 0 {
+                    assert!(graph.beats(from, to));
+                    assert!(!reachable(&reduced, from, Some((from, to)))[to], "{} -> {} is redundant", from, to);
+                }
+            }
+        }
+    }
+}
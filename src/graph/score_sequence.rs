@@ -0,0 +1,82 @@
+use std::fmt;
+
+/// Reason why a sequence fails Landau's conditions for a tournament score sequence.
+#[derive(Debug, PartialEq)]
+pub enum LandauViolation {
+    /// The `k` smallest scores sum up to less than `k(k-1)/2`, the number of games played among them.
+    PrefixTooSmall { k: usize, sum: i64, required: i64 },
+    /// All scores together must sum up to exactly `n(n-1)/2`, the number of games in the tournament.
+    WrongTotal { sum: i64, required: i64 },
+}
+
+impl fmt::Display for LandauViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LandauViolation::PrefixTooSmall { k, sum, required } =>
+                write!(f, "{} smallest scores sum up to {}, expected at least {}", k, sum, required),
+            LandauViolation::WrongTotal { sum, required } =>
+                write!(f, "scores sum up to {}, expected {}", sum, required),
+        }
+    }
+}
+
+/// Checks Landau's theorem: a sequence is the score sequence of some tournament if and only if,
+/// sorted in nondecreasing order, every prefix of length `k` sums up to at least `k(k-1)/2`
+/// and the whole sequence sums up to exactly `n(n-1)/2`. Order of the input does not matter.
+pub fn check_landau(scores: &[i32]) -> Result<(), LandauViolation> {
+    let mut sorted = scores.to_vec();
+    sorted.sort_unstable();
+
+    let mut sum: i64 = 0;
+    for (i, score) in sorted.iter().enumerate() {
+        let k = i + 1;
+        sum += *score as i64;
+        let required = games_among(k);
+        if sum < required {
+            return Err(LandauViolation::PrefixTooSmall { k, sum, required });
+        }
+    }
+
+    let required = games_among(sorted.len());
+    if sum != required {
+        return Err(LandauViolation::WrongTotal { sum, required });
+    }
+    Ok(())
+}
+
+fn games_among(k: usize) -> i64 {
+    (k * k.saturating_sub(1) / 2) as i64
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::score_sequence::{check_landau, LandauViolation};
+
+    #[test]
+    pub fn accepts_valid_sequences() {
+        assert!(check_landau(&[]).is_ok());
+        assert!(check_landau(&[0]).is_ok());
+        assert!(check_landau(&[1, 1, 1]).is_ok());
+        assert!(check_landau(&[0, 1, 2]).is_ok());
+        assert!(check_landau(&[2, 1, 1, 2]).is_ok());
+        assert!(check_landau(&[3, 2, 2, 1, 2]).is_ok());
+    }
+
+    #[test]
+    pub fn rejects_too_small_prefix() {
+        assert_eq!(
+            Err(LandauViolation::PrefixTooSmall { k: 2, sum: 0, required: 1 }),
+            check_landau(&[0, 0, 3])
+        );
+        assert!(check_landau(&[-1, 2, 2]).is_err());
+    }
+
+    #[test]
+    pub fn rejects_wrong_total() {
+        assert_eq!(
+            Err(LandauViolation::WrongTotal { sum: 4, required: 3 }),
+            check_landau(&[1, 1, 2])
+        );
+    }
+}
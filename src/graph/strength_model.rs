@@ -0,0 +1,118 @@
+use std::f64::consts::PI;
+use rand::Rng;
+use crate::graph::graph::Graph;
+use crate::matrix::matrix::Matrix;
+
+/// Model deciding who wins a game from the latent ratings of both players.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StrengthModel {
+    /// Ratings are positive strengths and `i` beats `j` with probability `r_i / (r_i + r_j)`.
+    BradleyTerry,
+    /// Ratings are Elo points and `i` beats `j` with probability `1 / (1 + 10^((r_j - r_i) / 400))`.
+    Elo,
+}
+
+impl StrengthModel {
+    pub fn default_spread(&self) -> f64 {
+        match self {
+            StrengthModel::BradleyTerry => 1.0,
+            StrengthModel::Elo => 200.0,
+        }
+    }
+
+    pub fn win_probability(&self, rating: f64, opponent_rating: f64) -> f64 {
+        match self {
+            StrengthModel::BradleyTerry => rating / (rating + opponent_rating),
+            StrengthModel::Elo => 1.0 / (1.0 + libm::pow(10.0, (opponent_rating - rating) / 400.0)),
+        }
+    }
+
+    /// Draws a rating of a single player. Bradley–Terry strengths are log-normal with `spread`
+    /// as the standard deviation of their logarithm, Elo ratings are normal around 1500.
+    pub fn sample_rating(&self, spread: f64, rng: &mut impl Rng) -> f64 {
+        let z = standard_normal(rng);
+        match self {
+            StrengthModel::BradleyTerry => libm::exp(spread * z),
+            StrengthModel::Elo => 1500.0 + spread * z,
+        }
+    }
+}
+
+/// Plays every game once, each won with the probability given by the model and the ratings.
+pub fn generate_with_ratings(ratings: &[f64], model: StrengthModel, rng: &mut impl Rng) -> Graph {
+    let size = ratings.len();
+    let mut graph = Graph::new(Matrix::new(size, size));
+    for i in 0..size {
+        for j in 0..i {
+            // Extreme ratings overflow to infinities, whose ratio is NaN
+            let p = model.win_probability(ratings[i], ratings[j]);
+            let p = if p.is_nan() { 0.5 } else { p.clamp(0.0, 1.0) };
+            if rng.gen_bool(p) {
+                graph.set_winner(i, j);
+            } else {
+                graph.set_winner(j, i);
+            }
+        }
+    }
+    graph
+}
+
+/// Samples a rating for every player and generates a league played according to them.
+/// Ratings and probabilities use the `libm` crate instead of the platform's math library,
+/// so a seed gives the same league everywhere.
+pub fn generate_league(size: usize, model: StrengthModel, spread: f64, rng: &mut impl Rng) -> Graph {
+    let ratings: Vec<f64> = (0..size).map(|_| model.sample_rating(spread, rng)).collect();
+    generate_with_ratings(&ratings, model, rng)
+}
+
+/// Box–Muller transform, so no distribution crate is needed for a single normal sample.
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen::<f64>();
+    (-2.0 * libm::log(u1)).sqrt() * libm::cos(2.0 * PI * u2)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::generator::seeded_rng;
+    use crate::graph::strength_model::{generate_league, generate_with_ratings, StrengthModel};
+
+    #[test]
+    pub fn equal_ratings_are_a_coin_flip() {
+        assert_eq!(0.5, StrengthModel::BradleyTerry.win_probability(2.0, 2.0));
+        assert_eq!(0.5, StrengthModel::Elo.win_probability(1500.0, 1500.0));
+    }
+
+    #[test]
+    pub fn elo_difference_of_400_gives_ten_to_one_odds() {
+        let p = StrengthModel::Elo.win_probability(1900.0, 1500.0);
+
+        assert!((p - 10.0 / 11.0).abs() < 1e-12);
+    }
+
+    #[test]
+    pub fn overwhelming_ratings_give_transitive_league() {
+        let ratings: Vec<f64> = (0..8).rev().map(|i| 1500.0 + 10000.0 * i as f64).collect();
+
+        let graph = generate_with_ratings(&ratings, StrengthModel::Elo, &mut seeded_rng(1, 0));
+
+        assert_eq!((0..8).rev().collect::<Vec<_>>(), graph.score_sequence());
+    }
+
+    #[test]
+    pub fn league_is_a_tournament() {
+        let graph = generate_league(30, StrengthModel::BradleyTerry, 1.0, &mut seeded_rng(5, 0));
+
+        assert!(graph.is_tournament());
+    }
+
+    #[test]
+    pub fn extreme_spreads_still_give_tournaments() {
+        for (model, spread) in [(StrengthModel::BradleyTerry, 1000.0), (StrengthModel::Elo, 1e308)] {
+            let graph = generate_league(10, model, spread, &mut seeded_rng(1, 0));
+
+            assert!(graph.is_tournament());
+        }
+    }
+}
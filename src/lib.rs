@@ -0,0 +1,5 @@
+pub mod data_parser;
+pub mod format;
+pub mod graph;
+pub mod matrix;
+pub mod ping_pong;
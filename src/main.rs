@@ -1,21 +1,37 @@
-use std::fs::File;
-use std::io::Write;
+use std::io::{BufRead, Write};
 use std::process::exit;
+use std::time::Instant;
 
 use crate::console::parse_console_arguments;
-use crate::console_arguments::{ConsoleArguments, MultiplicationMethod};
-use crate::data_parser::DataParser;
-use crate::graph::generator::generate_ping_pong;
-use crate::graph::graph::Graph;
-use crate::matrix::naive_mul::naive_mul;
-use crate::matrix::strassen_mul::strassen_mul;
-use crate::matrix::mixed_mul::mixed_mul;
-use crate::ping_pong::ping_pong_solver::PingPongSolver;
-
-mod data_parser;
-mod graph;
-mod matrix;
-mod ping_pong;
+use crate::console_arguments::{BenchGraphs, ConsoleArguments, ExportFormat, GenerateArguments, GenerationMethod, MultiplicationMethod};
+use ping_pong::format::dot_format::{write_dot, DotOptions};
+use ping_pong::format::graph_format::{read_graphs, GraphFormat, GraphWriter};
+use ping_pong::format::solution_format::{Solution, SolutionFormat, SolutionRecord, SolutionWriter};
+use ping_pong::format::stream::{create_output, open_input, Output, STANDARD_STREAM};
+use ping_pong::graph::generator::{
+    generate_doubly_regular,
+    generate_from_scores,
+    generate_paley,
+    generate_ping_pong,
+    generate_regular,
+    generate_rotational,
+    generate_transitive,
+    generate_upset,
+    reverse_random_3_cycles,
+    seeded_rng,
+    GeneratorError
+};
+use ping_pong::graph::graph::{DrawSemantics, Graph, PairResult};
+use ping_pong::graph::score_sequence::check_landau;
+use ping_pong::graph::strength_model::generate_league;
+use ping_pong::matrix::matrix::Matrix;
+use ping_pong::matrix::mixed_mul::mixed_mul;
+use ping_pong::ping_pong::explanation::{explain_king, Reach};
+use ping_pong::ping_pong::benchmark::{benchmark, write_csv_header, write_csv_row, write_table_header, write_table_row};
+use ping_pong::ping_pong::cross_check::cross_check;
+use ping_pong::ping_pong::ping_pong_solver::PingPongSolver;
+use ping_pong::ping_pong::verification::{verify_kings, Discrepancy};
+
 mod console;
 mod console_arguments;
 
@@ -25,81 +41,359 @@ fn main() {
     match arguments {
         ConsoleArguments::Solve(solve_args) => {
 
-            let graphs: Vec<_> = solve_args.input_files.iter().map(|input| {
-                match DataParser::parse_graph_input(&input) {
-                    Ok(graphs) => graphs,
-                    Err(e) => {
-                        eprintln!("IO error: {}", e);
+            let solver = PingPongSolver::new(solve_args.mul_method.algorithm())
+                .with_draw_semantics(solve_args.draws)
+                .with_margin(solve_args.margin.unwrap_or(1));
+            let methods: Vec<_> = MultiplicationMethod::ALL.iter()
+                .map(|method| (method.to_string(), method.algorithm()))
+                .collect();
+
+            // Plain solutions go to the standard output as lists unless an output file is given
+            let mut output = match (&solve_args.output_file, solve_args.output_format) {
+                (None, SolutionFormat::Plain) => None,
+                (output, format) => {
+                    let output = output.as_deref().unwrap_or(STANDARD_STREAM);
+                    let out = create_output(output).unwrap_or_else(|e| {
+                        eprintln!("Error creating {}: {}", output, e);
+                        exit(1);
+                    });
+                    Some(SolutionWriter::new(out, format))
+                }
+            };
+            let output_name = solve_args.output_file.as_deref();
+            // Records on the standard output are not mixed with the text of --verbose and --explain
+            let to_stderr = output.is_some() && output_name.is_none_or(|name| name == STANDARD_STREAM);
+
+            // Results are written as soon as a graph is solved, so only one graph is held in memory
+            for (file, index, g) in input_graphs(&solve_args.input_files, solve_args.input_format) {
+                if solve_args.cross_check {
+                    if let Err(divergence) = cross_check(&g, &methods, solve_args.draws, solve_args.margin.unwrap_or(1)) {
+                        eprintln!("Cross-check failed on graph {} of {}: {}", index + 1, file, divergence);
                         exit(1);
                     }
-                }}).flatten().collect();
+                }
+                if solve_args.verbose { report(to_stderr, format_args!("{:}", g)); }
+                let print = solve_args.verbose || output.is_none();
+                let start = Instant::now();
+                let solution = if solve_args.partial {
+                    let solution = solver.solve_partial(&g);
+                    let elapsed = start.elapsed();
+                    if print {
+                        report(to_stderr, format_args!("Certain: {} Possible: {}\n", format_king_list(&g, &solution.certain), format_king_list(&g, &solution.possible)));
+                    }
+                    (Solution::Partial { certain: king_names(&g, &solution.certain), possible: king_names(&g, &solution.possible) }, elapsed)
+                } else {
+                    let kings = solver.solve(&g);
+                    let elapsed = start.elapsed();
+                    if print { report(to_stderr, format_args!("{}\n", format_king_list(&g, &kings))); }
+                    if solve_args.explain {
+                        print_explanation(&g, &kings, &solver.beat_matrix(&g), to_stderr);
+                    }
+                    (Solution::Kings(king_names(&g, &kings)), elapsed)
+                };
+                if solve_args.verbose { report(to_stderr, format_args!("\n")) }
+                if let Some(out) = &mut output {
+                    let (solution, elapsed) = solution;
+                    let record = SolutionRecord {
+                        file,
+                        graph: index + 1,
+                        vertices: g.vertices(),
+                        solution,
+                        method: solve_args.mul_method.to_string(),
+                        elapsed,
+                    };
+                    write_or_exit(out.write(&record), output_name);
+                }
+            }
+            if let Some(out) = output {
+                write_or_exit(out.finish().and_then(Output::finish), output_name);
+            }
+        }
+        ConsoleArguments::Generate(generate_args) => {
+            let seed = generate_args.seed.unwrap_or_else(rand::random);
+            eprintln!("Seed: {}", seed);
+
+            let out = create_output(&generate_args.output_file).unwrap_or_else(|e| {
+                eprintln!("Error creating {}: {}", generate_args.output_file, e);
+                exit(1);
+            });
+            let mut writer = GraphWriter::new(out, generate_args.output_format);
+            for (index, graph) in generate_graphs(&generate_args, seed).enumerate() {
+                let graph = graph.unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    exit(1);
+                });
+                let header = generate_args.metadata.then(|| generated_header(&generate_args, seed, index, &graph));
+                write_or_exit(writer.write(&graph, header.as_deref()), Some(&generate_args.output_file));
+            }
+            write_or_exit(writer.finish().and_then(Output::finish), Some(&generate_args.output_file));
+        }
+        ConsoleArguments::Stats(stats_args) => {
+            let solver = PingPongSolver::new(mixed_mul).with_draw_semantics(stats_args.draws);
 
-            let solver = PingPongSolver::new(match solve_args.mul_method {
-                MultiplicationMethod::Naive => naive_mul,
-                MultiplicationMethod::Strassen => strassen_mul,
-                MultiplicationMethod::Mixed => mixed_mul
+            for (i, (_, _, graph)) in input_graphs(&stats_args.input_files, stats_args.input_format).enumerate() {
+                print_stats(i, &graph, &solver, stats_args.draws);
+            }
+        }
+        ConsoleArguments::Export(export_args) => {
+            let solver = PingPongSolver::new(mixed_mul).with_draw_semantics(export_args.draws);
+            let mut out = create_output(&export_args.output_file).unwrap_or_else(|e| {
+                eprintln!("Error creating {}: {}", export_args.output_file, e);
+                exit(1);
             });
 
-            let results: Vec<_> = graphs.iter()
-                .map(|g| {
-                    if solve_args.verbose { print!("{:}", g); }
-                    let result = solver.solve(&g);
-                    if solve_args.verbose || solve_args.output_file.is_none() {
-                        println!("{:?}", result.iter().map(|i| i+1).collect::<Vec<_>>());
-                        if solve_args.verbose { println!() }
+            for (i, (_, _, graph)) in input_graphs(&export_args.input_files, export_args.input_format).enumerate() {
+                let kings = solver.solve(&graph);
+                if i != 0 { write_or_exit(writeln!(out), Some(&export_args.output_file)); }
+                match export_args.format {
+                    ExportFormat::Dot => {
+                        let options = DotOptions {
+                            layout: export_args.layout,
+                            transitive_reduction: export_args.transitive_reduction,
+                            draws: export_args.draws,
+                        };
+                        write_or_exit(write_dot(&mut out, i, &graph, &kings, &options), Some(&export_args.output_file));
                     }
-                    result
-                })
-                .collect();
+                }
+            }
+            write_or_exit(out.finish(), Some(&export_args.output_file));
+        }
+        ConsoleArguments::Verify(verify_args) => {
+            let solver = PingPongSolver::new(mixed_mul)
+                .with_draw_semantics(verify_args.draws)
+                .with_margin(verify_args.margin.unwrap_or(1));
+            let results: Vec<String> = open_input(&verify_args.results_file)
+                .and_then(|reader| reader.lines().collect())
+                .unwrap_or_else(|e| {
+                    eprintln!("Error reading {}: {}", verify_args.results_file, e);
+                    exit(1);
+                });
 
-            match solve_args.output_file {
-                None => {}
-                Some(output) => {
-                    save_results_to_file(results, output);
+            let mut mismatches = 0;
+            let mut graphs = 0;
+            for (i, (_, _, graph)) in input_graphs(&verify_args.input_files, verify_args.input_format).enumerate() {
+                graphs += 1;
+                let Some(line) = results.get(i) else { continue };
+                if !verify_result(i, &graph, line, &solver) {
+                    mismatches += 1;
                 }
             }
+            if results.len() != graphs {
+                println!("{} holds results for {} graphs, the inputs hold {}", verify_args.results_file, results.len(), graphs);
+                mismatches += 1;
+            }
+            if mismatches != 0 {
+                exit(1);
+            }
         }
-        ConsoleArguments::Generate(generate_args) => {
-            let graphs = generate_args.sizes.iter().map(|size| generate_ping_pong(*size, 0.5)).collect();
-            save_graphs_to_file(graphs, generate_args.output_file);
+        ConsoleArguments::Bench(bench_args) => {
+            let graphs: Box<dyn Iterator<Item = Graph>> = match &bench_args.graphs {
+                BenchGraphs::Sizes { sizes, seed } => {
+                    let seed = seed.unwrap_or_else(rand::random);
+                    eprintln!("Seed: {}", seed);
+                    Box::new(sizes.iter().enumerate()
+                        .map(move |(index, size)| generate_ping_pong(*size, 0.5, &mut seeded_rng(seed, index as u64))))
+                }
+                BenchGraphs::Files { input_files, input_format } =>
+                    Box::new(input_graphs(input_files, *input_format).map(|(_, _, graph)| graph)),
+            };
+            let mut csv = bench_args.csv_file.as_ref().map(|csv_file| {
+                let mut out = create_output(csv_file).unwrap_or_else(|e| {
+                    eprintln!("Error creating {}: {}", csv_file, e);
+                    exit(1);
+                });
+                write_or_exit(write_csv_header(&mut out), Some(csv_file));
+                out
+            });
+
+            let mut table = std::io::stdout();
+            write_or_exit(write_table_header(&mut table), None);
+            for graph in graphs {
+                for method in &bench_args.methods {
+                    let result = benchmark(&graph, method.to_string(), method.algorithm(), bench_args.repeat);
+                    write_or_exit(write_table_row(&mut table, &result), None);
+                    if let Some(out) = &mut csv {
+                        write_or_exit(write_csv_row(out, &result), bench_args.csv_file.as_deref());
+                    }
+                }
+            }
+            if let Some(out) = csv {
+                write_or_exit(out.finish(), bench_args.csv_file.as_deref());
+            }
+        }
+        ConsoleArguments::Convert(convert_args) => {
+            let out = create_output(&convert_args.output_file).unwrap_or_else(|e| {
+                eprintln!("Error creating {}: {}", convert_args.output_file, e);
+                exit(1);
+            });
+            let mut writer = GraphWriter::new(out, convert_args.output_format);
+            for (_, _, graph) in input_graphs(&convert_args.input_files, convert_args.input_format) {
+                write_or_exit(writer.write(&graph, None), Some(&convert_args.output_file));
+            }
+            write_or_exit(writer.finish().and_then(Output::finish), Some(&convert_args.output_file));
         }
     }
 }
 
-fn save_graphs_to_file(graphs: Vec<Graph>, output: String) {
-    let file = match File::create(output) {
-        Ok(file) => file,
-        Err(_) => {
-            eprintln!("Error creating file");
-            return;
-        }
+/// Sizes of all graphs to generate, in order. The position of a graph in this list
+/// is also the stream of the random number generator it is generated with.
+fn generated_sizes(args: &GenerateArguments) -> Vec<usize> {
+    let sizes = match &args.method {
+        GenerationMethod::ScoreSequence { scores, .. } => vec![scores.len()],
+        _ => args.sizes.iter().map(|size| *size as usize).collect(),
     };
+    sizes.into_iter().flat_map(|size| std::iter::repeat_n(size, args.count)).collect()
+}
 
-    for (i, graph) in graphs.iter().enumerate() {
-        for row in 0..graph.adj_matrix.rows() {
-            for col in 0..graph.adj_matrix.cols() {
-                write!(&file, "{:>2} ", graph.adj_matrix[col][row]).expect("Failed to write");
+/// Graphs to generate, in order, each generated only when it is consumed.
+fn generate_graphs(args: &GenerateArguments, seed: u64) -> impl Iterator<Item = Result<Graph, GeneratorError>> + '_ {
+    generated_sizes(args).into_iter().enumerate().map(move |(index, size)| {
+        let mut rng = seeded_rng(seed, index as u64);
+        match &args.method {
+            GenerationMethod::Random { probability } => Ok(generate_ping_pong(size as i32, *probability, &mut rng)),
+            GenerationMethod::League { model, spread } => Ok(generate_league(size, *model, *spread, &mut rng)),
+            GenerationMethod::Transitive => Ok(generate_transitive(size)),
+            GenerationMethod::Regular => generate_regular(size),
+            GenerationMethod::DoublyRegular => generate_doubly_regular(size),
+            GenerationMethod::Paley => generate_paley(size),
+            GenerationMethod::Rotational { connection_set } => generate_rotational(size, connection_set),
+            GenerationMethod::Upset { upsets } => generate_upset(size, *upsets, &mut rng),
+            GenerationMethod::ScoreSequence { scores, randomize } => {
+                let mut graph = generate_from_scores(scores)?;
+                if *randomize {
+                    reverse_random_3_cycles(&mut graph, size * size, &mut rng);
+                }
+                Ok(graph)
             }
-            writeln!(&file).expect("Failed to write");
         }
-        if i != graphs.len()-1 { writeln!(&file).expect("Failed to write") };
-    }
+    })
+}
+
+fn generated_header(args: &GenerateArguments, seed: u64, index: usize, graph: &Graph) -> String {
+    format!("size={} {} seed={} stream={}", graph.vertices(), args.method, seed, index)
+}
+
+/// Graphs of all input files in order, read lazily. Exits on the first file that cannot be read.
+/// Every graph is returned with its file and its position in the file.
+fn input_graphs(input_files: &[String], input_format: GraphFormat) -> impl Iterator<Item = (&str, usize, Graph)> + '_ {
+    input_files.iter().flat_map(move |input| {
+        let reader = open_input(input).unwrap_or_else(|e| {
+            eprintln!("Error reading {}: {}", input, e);
+            exit(1);
+        });
+        read_graphs(input_format, reader).enumerate().map(move |(index, graph)| {
+            let graph = graph.unwrap_or_else(|e| {
+                eprintln!("Error reading {}: {}", input, e);
+                exit(1);
+            });
+            (input.as_str(), index, graph)
+        })
+    })
 }
 
-fn save_results_to_file(results: Vec<Vec<i32>>, output: String) {
-    let formatted_string: String = results
-        .iter()
-        .map(|inner_vec| inner_vec.iter().map(|i|i+1).map(|arg0: i32| ToString::to_string(&arg0)).collect::<Vec<String>>().join(" "))
-        .chain(vec![String::new()])
-        .collect::<Vec<String>>()
-        .join("\n");
-
-    // Write the string to a file
-    if let Ok(mut file) = File::create(output) {
-        if let Err(err) = file.write_all(formatted_string.as_bytes()) {
-            eprintln!("Error writing to file: {}", err);
+/// Checks one result line against its graph and reports every discrepancy. Returns whether the line is correct.
+fn verify_result(index: usize, graph: &Graph, line: &str, solver: &PingPongSolver) -> bool {
+    let mut problems = Vec::new();
+    let claims: Vec<&str> = line.split('|').collect();
+    let checks = if claims.len() == 2 {
+        vec![("certain", claims[0], solver.beat_matrix(graph).into_owned()), ("possible", claims[1], solver.optimistic_matrix(graph))]
+    } else {
+        vec![("", line, solver.beat_matrix(graph).into_owned())]
+    };
+    for (kind, claim, adj) in checks {
+        let mut claimed = Vec::new();
+        for name in claim.split_whitespace() {
+            match (0..graph.vertices()).find(|v| graph.vertex_name(*v) == name) {
+                Some(v) => claimed.push(v),
+                None => problems.push(format!("unknown player {}", name)),
+            }
         }
+        let kind = if kind.is_empty() { String::new() } else { format!("{} ", kind) };
+        for discrepancy in verify_kings(&adj, &claimed) {
+            problems.push(match discrepancy {
+                Discrepancy::Spurious { king, unreached } => format!(
+                    "spurious {}king {}: does not reach {} in two steps", kind, graph.vertex_name(king), graph.vertex_name(unreached)),
+                Discrepancy::Missing { king, witness } => format!(
+                    "missing {}king {}: {}", kind, graph.vertex_name(king),
+                    witness.iter().map(|reach| describe_reach(graph, reach)).collect::<Vec<_>>().join(", ")),
+            });
+        }
+    }
+
+    if problems.is_empty() {
+        println!("Graph {}: ok", index + 1);
     } else {
-        eprintln!("Error creating file");
+        println!("Graph {}: mismatch", index + 1);
+        for problem in &problems {
+            println!("  {}", problem);
+        }
+    }
+    problems.is_empty()
+}
+
+fn describe_reach(graph: &Graph, reach: &Reach) -> String {
+    match reach {
+        Reach::Direct { target } => format!("beats {}", graph.vertex_name(*target)),
+        Reach::Through { via, target } => format!("reaches {} through {}", graph.vertex_name(*target), graph.vertex_name(*via)),
+    }
+}
+
+fn write_or_exit(written: Result<(), std::io::Error>, output: Option<&str>) {
+    if let Err(e) = written {
+        eprintln!("Error writing to {}: {}", output.unwrap_or("standard output"), e);
+        exit(1);
+    }
+}
+
+fn print_stats(index: usize, graph: &Graph, solver: &PingPongSolver, draws: DrawSemantics) {
+    let scores = graph.scores(draws);
+    let kings = solver.solve(graph);
+
+    println!("Graph {} V:{}", index + 1, graph.vertices());
+    println!("Scores: [{}]", scores.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", "));
+    if !graph.is_tournament() {
+        println!("Not a tournament, the max-score vertex need not be a king");
+    }
+    if let Err(violation) = check_landau(&graph.score_sequence()) {
+        println!("Wins are not a tournament score sequence: {}", violation);
+    }
+    println!("Kings: {}", kings.len());
+    match graph.max_score_vertex(draws) {
+        Some(v) if kings.contains(&(v as i32)) => println!("Max-score king: {} (score {})", graph.vertex_name(v), scores[v]),
+        Some(v) => println!("Max-score vertex: {} (score {}) is not a king", graph.vertex_name(v), scores[v]),
+        None => println!("Max-score king: none"),
     }
+    println!();
+}
+
+fn print_explanation(graph: &Graph, kings: &[i32], beat_matrix: &Matrix<i32>, to_stderr: bool) {
+    for king in kings {
+        let king = *king as usize;
+        report(to_stderr, format_args!("King {}:\n", graph.vertex_name(king)));
+        for reach in explain_king(beat_matrix, king).expect("kings reach everyone") {
+            match reach {
+                Reach::Direct { target } if graph.result(king, target) == Some(PairResult::Draw) =>
+                    report(to_stderr, format_args!("  drew with {} (counted as a win)\n", graph.vertex_name(target))),
+                Reach::Direct { target } =>
+                    report(to_stderr, format_args!("  beats {} (margin {})\n", graph.vertex_name(target), graph.margin(king, target))),
+                Reach::Through { via, target } =>
+                    report(to_stderr, format_args!("  reaches {} through {} (margins {}, {})\n",
+                             graph.vertex_name(target), graph.vertex_name(via), graph.margin(king, via), graph.margin(via, target))),
+            }
+        }
+    }
+}
+
+/// Prints text meant for people, on the standard error when the standard output carries solution records.
+fn report(to_stderr: bool, text: std::fmt::Arguments) {
+    if to_stderr { eprint!("{}", text) } else { print!("{}", text) }
+}
+
+/// Kings as a bracketed list for the standard output, e.g. `[1, 2, 4]`.
+fn format_king_list(graph: &Graph, kings: &[i32]) -> String {
+    format!("[{}]", kings.iter().map(|i| graph.vertex_name(*i as usize)).collect::<Vec<String>>().join(", "))
+}
+
+fn king_names(graph: &Graph, kings: &[i32]) -> Vec<String> {
+    kings.iter().map(|i| graph.vertex_name(*i as usize)).collect()
 }
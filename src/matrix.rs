@@ -1,6 +1,8 @@
+#[allow(clippy::module_inception)]
 pub mod matrix;
 pub mod naive_mul;
 pub mod strassen_mul;
 pub mod mixed_mul;
-
-mod generic_strassen;
+pub mod generic_strassen;
+#[cfg(test)]
+pub mod strategy;
@@ -1,11 +1,13 @@
 use crate::matrix::matrix::Matrix;
 
-pub fn generic_strassen(a: &Matrix<i32>, b: &Matrix<i32>, mul: &dyn Fn(&Matrix<i32>, &Matrix<i32>) -> Matrix<i32>) -> Matrix<i32> {
+type Multiplication = dyn Fn(&Matrix<i32>, &Matrix<i32>) -> Matrix<i32>;
+
+pub fn generic_strassen(a: &Matrix<i32>, b: &Matrix<i32>, mul: &Multiplication) -> Matrix<i32> {
     let (a11, a12, a21, a22);
     let (b11, b12, b21, b22);
 
-    (a11, a12, a21, a22) = explode_matrix_to_4(&a);
-    (b11, b12, b21, b22) = explode_matrix_to_4(&b);
+    (a11, a12, a21, a22) = explode_matrix_to_4(a);
+    (b11, b12, b21, b22) = explode_matrix_to_4(b);
 
     let mut tmp1 = Matrix::new(a11.cols(), a11.rows());
     let mut tmp2 = Matrix::new(a11.cols(), a11.rows());
@@ -52,21 +54,24 @@ pub fn generic_strassen(a: &Matrix<i32>, b: &Matrix<i32>, mul: &dyn Fn(&Matrix<i
     // Third argument
     Matrix::add(&s4, &s5, &mut tmp3);
 
-    return connect_4_matrices(
+    connect_4_matrices(
         &tmp1,
         &tmp2,
         &tmp3,
         &tmp4,
-        a.cols() % 2 != 0
-    );
+        !a.cols().is_multiple_of(2)
+    )
 }
 
 
-fn explode_matrix_to_4(m: &Matrix<i32>) -> (Matrix<i32>, Matrix<i32>, Matrix<i32>, Matrix<i32>) {
+/// Splits a square matrix into its four quadrants `(m11, m12, m21, m22)`. Quadrants are indexed
+/// like `m[col][row]`, so `m12` takes the first half of the columns and the second half of the rows.
+/// Odd sizes are padded with zeros to equal quadrants.
+pub fn explode_matrix_to_4(m: &Matrix<i32>) -> (Matrix<i32>, Matrix<i32>, Matrix<i32>, Matrix<i32>) {
     let (mut m11, mut m12, mut m21, mut m22);
 
-    if m.cols() % 2 != 0 {
-        let size = (m.cols() + 1)/2;
+    if !m.cols().is_multiple_of(2) {
+        let size = m.cols().div_ceil(2);
 
         m11 = Matrix::new(size, size);
         m12 = Matrix::new(size, size);
@@ -113,7 +118,9 @@ fn explode_matrix_to_4(m: &Matrix<i32>) -> (Matrix<i32>, Matrix<i32>, Matrix<i32
 }
 
 
-fn connect_4_matrices(m11: &Matrix<i32>, m12: &Matrix<i32>, m21: &Matrix<i32>, m22: &Matrix<i32>, compress: bool) -> Matrix<i32> {
+/// Joins four quadrants as split by `explode_matrix_to_4`. With `compress` the padding row
+/// and column of an odd size are dropped again.
+pub fn connect_4_matrices(m11: &Matrix<i32>, m12: &Matrix<i32>, m21: &Matrix<i32>, m22: &Matrix<i32>, compress: bool) -> Matrix<i32> {
     let mut result;
     let m11_cols = m11.cols();
     let m11_rows = m11.rows();
@@ -172,11 +179,15 @@ fn connect_4_matrices(m11: &Matrix<i32>, m12: &Matrix<i32>, m21: &Matrix<i32>, m
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use crate::matrix::matrix::Matrix;
     use crate::matrix::generic_strassen::{
         connect_4_matrices,
-        explode_matrix_to_4
+        explode_matrix_to_4,
+        generic_strassen
     };
+    use crate::matrix::naive_mul::naive_mul;
+    use crate::matrix::strategy::{square_matrix, square_matrix_pair};
 
 
     #[test]
@@ -276,4 +287,20 @@ mod tests {
 
         assert_eq!(m1, m2)
     }
+
+    proptest! {
+        #[test]
+        fn exploding_and_connecting_round_trips(m in square_matrix(0..=33)) {
+            let odd = m.cols() % 2 == 1;
+
+            let (m11, m12, m21, m22) = explode_matrix_to_4(&m);
+
+            prop_assert_eq!(m, connect_4_matrices(&m11, &m12, &m21, &m22, odd));
+        }
+
+        #[test]
+        fn one_strassen_step_equals_naive_mul((a, b) in square_matrix_pair(0..=33)) {
+            prop_assert_eq!(naive_mul(&a, &b), generic_strassen(&a, &b, &naive_mul));
+        }
+    }
 }
@@ -187,7 +187,7 @@ mod tests {
     fn zeros_on_new() {
         let rows = 3;
         let cols = 4;
-        let m: Matrix<i32> = Matrix::new(cols as usize, rows as usize);
+        let m: Matrix<i32> = Matrix::new(cols, rows);
 
         for row in 0..rows {
             for col in 0..cols {
@@ -8,14 +8,33 @@ pub fn mixed_mul(a: &Matrix<i32>, b: &Matrix<i32>) -> Matrix<i32> {
     assert_eq!(a.cols(), a.rows(), "Only square matrices are supported");
     assert_eq!(b.cols(), b.rows(), "Only square matrices are supported");
 
-    return mixed_mul_impl(a, b);
+    mixed_mul_impl(a, b)
 }
 
 
 fn mixed_mul_impl(a: &Matrix<i32>, b: &Matrix<i32>) -> Matrix<i32> {
     if a.cols() <= 32 {
-        return naive_mul(&a, &b);
+        return naive_mul(a, b);
     }
     
-    return generic_strassen(&a, &b, &mixed_mul_impl);
+    generic_strassen(a, b, &mixed_mul_impl)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use crate::matrix::mixed_mul::mixed_mul;
+    use crate::matrix::naive_mul::naive_mul;
+    use crate::matrix::strategy::square_matrix_pair;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        // Sizes above 32 go through at least one Strassen step before falling back to naive_mul.
+        #[test]
+        fn equals_naive_mul((a, b) in square_matrix_pair(0..=80)) {
+            prop_assert_eq!(naive_mul(&a, &b), mixed_mul(&a, &b));
+        }
+    }
 }
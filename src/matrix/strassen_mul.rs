@@ -7,11 +7,15 @@ pub fn strassen_mul(a: &Matrix<i32>, b: &Matrix<i32>) -> Matrix<i32> {
     assert_eq!(a.cols(), a.rows(), "Only square matrices are supported");
     assert_eq!(b.cols(), b.rows(), "Only square matrices are supported");
 
-    return strassen_mul_impl(a, b);
+    strassen_mul_impl(a, b)
 }
 
 
 fn strassen_mul_impl(a: &Matrix<i32>, b: &Matrix<i32>) -> Matrix<i32> {
+    // Splitting an empty matrix gives empty quadrants again
+    if a.cols() == 0 {
+        return Matrix::new(0, 0);
+    }
     if a.cols() == 1 {
         let mut result =  Matrix::new(1, 1);
         result[0][0] = a[0][0] * b[0][0];
@@ -19,13 +23,16 @@ fn strassen_mul_impl(a: &Matrix<i32>, b: &Matrix<i32>) -> Matrix<i32> {
         return result;
     }
 
-    return generic_strassen(a, b, &strassen_mul_impl);
+    generic_strassen(a, b, &strassen_mul_impl)
 }
 
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use crate::matrix::matrix::Matrix;
+    use crate::matrix::naive_mul::naive_mul;
+    use crate::matrix::strategy::square_matrix_pair;
     use crate::matrix::strassen_mul::strassen_mul;
 
 
@@ -59,6 +66,13 @@ mod tests {
     }
 
 
+    #[test]
+    pub fn empty_matrices_give_empty_product() {
+        let empty = Matrix::new(0, 0);
+
+        assert_eq!(empty, strassen_mul(&empty, &empty));
+    }
+
     #[test]
     pub fn mut_returns_matrix_with_same_size_as_arguments() {
         for size in 1..=100 {
@@ -71,4 +85,14 @@ mod tests {
             assert_eq!(mul.rows(), size);
         }
     }
+
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn equals_naive_mul((a, b) in square_matrix_pair(0..=40)) {
+            prop_assert_eq!(naive_mul(&a, &b), strassen_mul(&a, &b));
+        }
+    }
 }
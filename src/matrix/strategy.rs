@@ -0,0 +1,18 @@
+//! Proptest strategies generating matrices for the property tests.
+use proptest::collection::vec;
+use proptest::prelude::*;
+use crate::matrix::matrix::Matrix;
+
+/// Square matrix with a side in `sizes` and small entries, positive and negative,
+/// so products cannot overflow.
+pub fn square_matrix(sizes: std::ops::RangeInclusive<usize>) -> impl Strategy<Value = Matrix<i32>> {
+    sizes.prop_flat_map(|size| vec(-9..=9, size * size).prop_map(move |data| {
+        // `with_flat_data` cannot tell the columns of an empty matrix
+        if size == 0 { Matrix::new(0, 0) } else { Matrix::with_flat_data(size, data) }
+    }))
+}
+
+/// Two square matrices of the same size.
+pub fn square_matrix_pair(sizes: std::ops::RangeInclusive<usize>) -> impl Strategy<Value = (Matrix<i32>, Matrix<i32>)> {
+    sizes.prop_flat_map(|size| (square_matrix(size..=size), square_matrix(size..=size)))
+}
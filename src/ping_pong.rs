@@ -1 +1,5 @@
 pub mod ping_pong_solver;
+pub mod benchmark;
+pub mod cross_check;
+pub mod explanation;
+pub mod verification;
@@ -0,0 +1,120 @@
+use std::io;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use crate::graph::graph::Graph;
+use crate::ping_pong::ping_pong_solver::{MultiplicationAlgorithm, PingPongSolver};
+
+/// Durations of repeated runs of the same work.
+#[derive(Debug)]
+pub struct Timing {
+    samples: Vec<Duration>,
+}
+
+impl Timing {
+    /// Runs `work` once to warm up and then `repeat` times, measuring every run.
+    pub fn measure<T>(repeat: usize, mut work: impl FnMut() -> T) -> Timing {
+        std::hint::black_box(work());
+        let samples = (0..repeat)
+            .map(|_| {
+                let start = Instant::now();
+                std::hint::black_box(work());
+                start.elapsed()
+            })
+            .collect();
+        Timing { samples }
+    }
+
+    fn millis(&self) -> impl Iterator<Item = f64> + '_ {
+        self.samples.iter().map(|d| d.as_secs_f64() * 1000.0)
+    }
+
+    pub fn mean_ms(&self) -> f64 {
+        self.millis().sum::<f64>() / self.samples.len() as f64
+    }
+
+    /// Sample standard deviation, zero for a single run.
+    pub fn stddev_ms(&self) -> f64 {
+        if self.samples.len() < 2 {
+            return 0.0;
+        }
+        let mean = self.mean_ms();
+        let variance = self.millis().map(|t| (t - mean).powi(2)).sum::<f64>() / (self.samples.len() - 1) as f64;
+        variance.sqrt()
+    }
+
+    pub fn min_ms(&self) -> f64 {
+        self.millis().fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// Timings of one multiplication method on one graph.
+pub struct BenchmarkResult {
+    pub vertices: usize,
+    pub method: String,
+    /// Squaring the beat matrix only.
+    pub multiply: Timing,
+    /// The whole solve including the king search.
+    pub solve: Timing,
+}
+
+pub fn benchmark(graph: &Graph, method: String, mul: MultiplicationAlgorithm, repeat: usize) -> BenchmarkResult {
+    let solver = PingPongSolver::new(mul);
+    let a = solver.beat_matrix(graph);
+    BenchmarkResult {
+        vertices: graph.vertices(),
+        multiply: Timing::measure(repeat, || mul(&a, &a)),
+        solve: Timing::measure(repeat, || solver.solve(graph)),
+        method,
+    }
+}
+
+pub fn write_table_header<W: Write>(out: &mut W) -> Result<(), io::Error> {
+    writeln!(out, "{:>8} {:>10} {:>6} {:>14} {:>10} {:>14} {:>10}", "n", "method", "runs", "multiply [ms]", "± [ms]", "solve [ms]", "± [ms]")
+}
+
+pub fn write_table_row<W: Write>(out: &mut W, result: &BenchmarkResult) -> Result<(), io::Error> {
+    writeln!(
+        out, "{:>8} {:>10} {:>6} {:>14.3} {:>10.3} {:>14.3} {:>10.3}",
+        result.vertices, result.method, result.multiply.samples.len(),
+        result.multiply.mean_ms(), result.multiply.stddev_ms(), result.solve.mean_ms(), result.solve.stddev_ms()
+    )
+}
+
+pub fn write_csv_header<W: Write>(out: &mut W) -> Result<(), io::Error> {
+    writeln!(out, "n,method,runs,multiply_mean_ms,multiply_stddev_ms,multiply_min_ms,solve_mean_ms,solve_stddev_ms,solve_min_ms")
+}
+
+pub fn write_csv_row<W: Write>(out: &mut W, result: &BenchmarkResult) -> Result<(), io::Error> {
+    writeln!(
+        out, "{},{},{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6}",
+        result.vertices, result.method, result.multiply.samples.len(),
+        result.multiply.mean_ms(), result.multiply.stddev_ms(), result.multiply.min_ms(),
+        result.solve.mean_ms(), result.solve.stddev_ms(), result.solve.min_ms()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::ping_pong::benchmark::Timing;
+
+    #[test]
+    pub fn timing_statistics() {
+        let timing = Timing { samples: [1, 2, 3].map(Duration::from_millis).to_vec() };
+
+        assert!((timing.mean_ms() - 2.0).abs() < 1e-9);
+        assert!((timing.stddev_ms() - 1.0).abs() < 1e-9);
+        assert!((timing.min_ms() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn measures_every_repetition() {
+        let mut runs = 0;
+        let timing = Timing::measure(4, || runs += 1);
+
+        assert_eq!(5, runs);
+        assert_eq!(4, timing.samples.len());
+    }
+}
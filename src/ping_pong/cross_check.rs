@@ -0,0 +1,120 @@
+use std::fmt;
+
+use crate::graph::graph::{DrawSemantics, Graph};
+use crate::ping_pong::ping_pong_solver::{MultiplicationAlgorithm, PingPongSolver};
+
+/// First difference between a multiplication method and the reference method.
+#[derive(Debug, PartialEq)]
+pub enum Divergence {
+    /// Entry `row`, `col` of the squared beat matrix differs.
+    Square { reference: String, method: String, row: usize, col: usize, expected: i32, found: i32 },
+    Kings { reference: String, method: String, expected: Vec<i32>, found: Vec<i32> },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Divergence::Square { reference, method, row, col, expected, found } => write!(
+                f, "A² at row {}, column {} is {} with {} but {} with {}", row + 1, col + 1, expected, reference, found, method),
+            Divergence::Kings { reference, method, expected, found } => write!(
+                f, "kings are {:?} with {} but {:?} with {}", expected, reference, found, method),
+        }
+    }
+}
+
+/// Solves `graph` with every method and compares the square of the beat matrix and the kings
+/// with those of the first method. Partial graphs also compare their possible kings.
+pub fn cross_check(
+    graph: &Graph,
+    methods: &[(String, MultiplicationAlgorithm)],
+    draws: DrawSemantics,
+    margin: i32
+) -> Result<(), Divergence> {
+    let solver = |mul| PingPongSolver::new(mul).with_draw_semantics(draws).with_margin(margin);
+    let Some(((reference, reference_mul), others)) = methods.split_first() else { return Ok(()) };
+
+    let reference_solver = solver(*reference_mul);
+    let a = reference_solver.beat_matrix(graph);
+    let expected_square = reference_mul(&a, &a);
+    let expected_kings = reference_solver.solve(graph);
+    let expected_partial = graph.is_partial().then(|| reference_solver.solve_partial(graph));
+
+    for (method, mul) in others {
+        let square = mul(&a, &a);
+        for row in 0..a.rows() {
+            for col in 0..a.cols() {
+                if square[col][row] != expected_square[col][row] {
+                    return Err(Divergence::Square {
+                        reference: reference.clone(),
+                        method: method.clone(),
+                        row,
+                        col,
+                        expected: expected_square[col][row],
+                        found: square[col][row],
+                    });
+                }
+            }
+        }
+
+        let method_solver = solver(*mul);
+        let kings = method_solver.solve(graph);
+        let partial = graph.is_partial().then(|| method_solver.solve_partial(graph));
+        let differing = if kings != expected_kings {
+            Some((expected_kings.clone(), kings))
+        } else {
+            match (&expected_partial, partial) {
+                (Some(expected), Some(found)) if *expected != found => Some((expected.possible.clone(), found.possible)),
+                _ => None,
+            }
+        };
+        if let Some((expected, found)) = differing {
+            return Err(Divergence::Kings { reference: reference.clone(), method: method.clone(), expected, found });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::generator::{generate_ping_pong, seeded_rng};
+    use crate::graph::graph::DrawSemantics;
+    use crate::matrix::matrix::Matrix;
+    use crate::matrix::mixed_mul::mixed_mul;
+    use crate::matrix::naive_mul::naive_mul;
+    use crate::matrix::strassen_mul::strassen_mul;
+    use crate::ping_pong::cross_check::{cross_check, Divergence};
+    use crate::ping_pong::ping_pong_solver::MultiplicationAlgorithm;
+
+    fn off_by_one(a: &Matrix<i32>, b: &Matrix<i32>) -> Matrix<i32> {
+        let mut m = naive_mul(a, b);
+        m[1][0] += 1;
+        m
+    }
+
+    #[test]
+    pub fn backends_agree_on_odd_and_even_sizes() {
+        let methods = [
+            ("naive".to_string(), naive_mul as MultiplicationAlgorithm),
+            ("strassen".to_string(), strassen_mul),
+            ("mixed".to_string(), mixed_mul),
+        ];
+        for size in [1, 2, 3, 7, 16, 33] {
+            let graph = generate_ping_pong(size, 0.5, &mut seeded_rng(size as u64, 0));
+            assert_eq!(Ok(()), cross_check(&graph, &methods, DrawSemantics::Ignore, 1));
+        }
+    }
+
+    #[test]
+    pub fn reports_first_differing_cell() {
+        let methods = [
+            ("naive".to_string(), naive_mul as MultiplicationAlgorithm),
+            ("broken".to_string(), off_by_one),
+        ];
+        let graph = generate_ping_pong(4, 0.5, &mut seeded_rng(1, 0));
+
+        match cross_check(&graph, &methods, DrawSemantics::Ignore, 1) {
+            Err(Divergence::Square { row: 0, col: 1, method, .. }) => assert_eq!("broken", method),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::matrix::matrix::Matrix;
+
+/// Witness of a vertex reaching another one in at most two steps.
+#[derive(Debug, PartialEq)]
+pub enum Reach {
+    Direct { target: usize },
+    Through { via: usize, target: usize },
+}
+
+/// Finds how `from` reaches `target` in the adjacency matrix, preferring a direct win.
+pub fn find_reach(adj: &Matrix<i32>, from: usize, target: usize) -> Option<Reach> {
+    if adj[target][from] != 0 {
+        return Some(Reach::Direct { target });
+    }
+    (0..adj.cols())
+        .find(|via| *via != from && *via != target && adj[*via][from] != 0 && adj[target][*via] != 0)
+        .map(|via| Reach::Through { via, target })
+}
+
+/// Witnesses of a king reaching every other vertex. `None` if some vertex is out of reach.
+pub fn explain_king(adj: &Matrix<i32>, king: usize) -> Option<Vec<Reach>> {
+    (0..adj.cols())
+        .filter(|target| *target != king)
+        .map(|target| find_reach(adj, king, target))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::matrix::Matrix;
+    use crate::ping_pong::explanation::{explain_king, find_reach, Reach};
+
+    #[test]
+    pub fn explains_3_cycle() {
+        // 0 beats 1, 1 beats 2, 2 beats 0.
+        let adj = Matrix::with_data(vec![vec![0, 0, 1], vec![1, 0, 0], vec![0, 1, 0]]);
+
+        let explanation = explain_king(&adj, 0);
+
+        assert_eq!(Some(vec![Reach::Direct { target: 1 }, Reach::Through { via: 1, target: 2 }]), explanation);
+    }
+
+    #[test]
+    pub fn no_witness_for_unreachable_vertex() {
+        // 0 beats 1 and 2, nobody else wins.
+        let adj = Matrix::with_data(vec![vec![0, 0, 0], vec![1, 0, 0], vec![1, 0, 0]]);
+
+        assert_eq!(None, find_reach(&adj, 1, 0));
+        assert_eq!(None, explain_king(&adj, 1));
+    }
+}
@@ -1,19 +1,86 @@
-use crate::graph::graph::Graph;
+use std::borrow::Cow;
+use crate::graph::graph::{DrawSemantics, Graph};
 use crate::matrix::matrix::Matrix;
 
+/// Kings of a tournament with some games not played yet.
+#[derive(Debug, PartialEq)]
+pub struct PartialSolution {
+    /// Kings under every possible outcome of the unplayed games.
+    pub certain: Vec<i32>,
+    /// Kings under at least one possible outcome of the unplayed games.
+    pub possible: Vec<i32>,
+}
+
+/// Function squaring the beat matrix, e.g. `naive_mul` or `strassen_mul`.
+pub type MultiplicationAlgorithm = fn(&Matrix<i32>, &Matrix<i32>) -> Matrix<i32>;
+
 pub struct PingPongSolver {
-    multiplication_algorithm: fn(&Matrix<i32>, &Matrix<i32>) -> Matrix<i32>
+    multiplication_algorithm: MultiplicationAlgorithm,
+    draw_semantics: DrawSemantics,
+    margin: i32
 }
 
 impl PingPongSolver {
 
-    pub fn new(mul_alg: fn(&Matrix<i32>, &Matrix<i32>) -> Matrix<i32>) -> Self {
-        PingPongSolver{ multiplication_algorithm: mul_alg}
+    pub fn new(mul_alg: MultiplicationAlgorithm) -> Self {
+        PingPongSolver{ multiplication_algorithm: mul_alg, draw_semantics: DrawSemantics::Ignore, margin: 1 }
+    }
+
+    pub fn with_draw_semantics(mut self, draw_semantics: DrawSemantics) -> Self {
+        self.draw_semantics = draw_semantics;
+        self
+    }
+
+    /// Only wins of a series by at least `margin` games count as beating someone.
+    pub fn with_margin(mut self, margin: i32) -> Self {
+        self.margin = margin;
+        self
     }
 
     pub fn solve(&self, input: &Graph) -> Vec<i32> {
-        let a = &input.adj_matrix;
-        let a_sq = (self.multiplication_algorithm)(&a, &a);
+        self.kings(&self.beat_matrix(input))
+    }
+
+    /// Losing every unplayed game is the worst case for reaching others, so certain kings are
+    /// the kings of the played games alone. Possible kings are the kings of the matrix in which
+    /// both players of every unplayed game beat each other: each game only matters for reaching
+    /// in one direction, so some completion of the unplayed games realises all of these paths.
+    pub fn solve_partial(&self, input: &Graph) -> PartialSolution {
+        PartialSolution {
+            certain: self.kings(&self.beat_matrix(input)),
+            possible: self.kings(&self.optimistic_matrix(input)),
+        }
+    }
+
+    /// Beat matrix in which both players of every unplayed game beat each other.
+    pub fn optimistic_matrix(&self, input: &Graph) -> Matrix<i32> {
+        let mut optimistic = self.beat_matrix(input).into_owned();
+        for (a, b) in &input.unplayed {
+            optimistic[*a][*b] = 1;
+            optimistic[*b][*a] = 1;
+        }
+        optimistic
+    }
+
+    /// Adjacency matrix the kings are computed from, with the margin and draw semantics applied.
+    pub fn beat_matrix<'a>(&self, input: &'a Graph) -> Cow<'a, Matrix<i32>> {
+        let mut m = if self.margin > 1 {
+            Cow::Owned(input.dominance_matrix(self.margin))
+        } else {
+            Cow::Borrowed(&input.adj_matrix)
+        };
+        if self.draw_semantics == DrawSemantics::Mutual && !input.draws.is_empty() {
+            let m = m.to_mut();
+            for (a, b) in &input.draws {
+                m[*a][*b] = 1;
+                m[*b][*a] = 1;
+            }
+        }
+        m
+    }
+
+    fn kings(&self, a: &Matrix<i32>) -> Vec<i32> {
+        let a_sq = (self.multiplication_algorithm)(a, a);
         let n = a.rows();
         let mut result = Vec::new();
 
@@ -33,3 +100,101 @@ impl PingPongSolver {
         result
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use crate::graph::generator::{generate_ping_pong, seeded_rng};
+    use crate::graph::graph::{DrawSemantics, Graph};
+    use crate::matrix::matrix::Matrix;
+    use crate::matrix::mixed_mul::mixed_mul;
+    use crate::matrix::naive_mul::naive_mul;
+    use crate::matrix::strassen_mul::strassen_mul;
+    use crate::ping_pong::ping_pong_solver::{MultiplicationAlgorithm, PartialSolution, PingPongSolver};
+
+    const ALGORITHMS: [MultiplicationAlgorithm; 3] = [naive_mul, strassen_mul, mixed_mul];
+
+    /// Whether `king` beats every other vertex directly or through someone it beats.
+    fn reaches_all_in_two_steps(graph: &Graph, king: usize) -> bool {
+        let n = graph.vertices();
+        (0..n).filter(|v| *v != king).all(|v| {
+            graph.beats(king, v) || (0..n).any(|w| graph.beats(king, w) && graph.beats(w, v))
+        })
+    }
+
+    #[test]
+    pub fn partial_solution_of_complete_tournament_matches_kings() {
+        let mut graph = Graph::new(Matrix::new(3, 3));
+        graph.set_winner(0, 1);
+        graph.set_winner(1, 2);
+        graph.set_winner(0, 2);
+        let solver = PingPongSolver::new(naive_mul);
+
+        let solution = solver.solve_partial(&graph);
+
+        assert_eq!(PartialSolution { certain: vec![0], possible: vec![0] }, solution);
+    }
+
+    #[test]
+    pub fn unplayed_games_separate_certain_and_possible_kings() {
+        // 0 beats 1, 1 beats 2, the game between 0 and 2 was not played.
+        let mut graph = Graph::with_unplayed(Matrix::new(3, 3), vec![(2, 0)]);
+        graph.set_winner(0, 1);
+        graph.set_winner(1, 2);
+        let solver = PingPongSolver::new(naive_mul);
+
+        let solution = solver.solve_partial(&graph);
+
+        // 0 reaches 2 through 1 whatever happens. 1 and 2 become kings only if 2 beats 0.
+        assert_eq!(vec![0], solution.certain);
+        assert_eq!(vec![0, 1, 2], solution.possible);
+    }
+
+    #[test]
+    pub fn draw_semantics_change_kings() {
+        // 0 beats 1, 1 and 2 drew, 0 and 2 drew.
+        let mut graph = Graph::new(Matrix::new(3, 3));
+        graph.set_winner(0, 1);
+        graph.set_draw(1, 2);
+        graph.set_draw(0, 2);
+
+        let ignore = PingPongSolver::new(naive_mul).with_draw_semantics(DrawSemantics::Ignore);
+        let mutual = PingPongSolver::new(naive_mul).with_draw_semantics(DrawSemantics::Mutual);
+
+        assert_eq!(Vec::<i32>::new(), ignore.solve(&graph));
+        assert_eq!(vec![0, 1, 2], mutual.solve(&graph));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn kings_are_exactly_the_vertices_reaching_all_in_two_steps(size in 1..24i32, p in 0.0..=1.0f64, seed: u64) {
+            let graph = generate_ping_pong(size, p, &mut seeded_rng(seed, 0));
+            let expected: Vec<i32> = (0..graph.vertices())
+                .filter(|v| reaches_all_in_two_steps(&graph, *v))
+                .map(|v| v as i32)
+                .collect();
+
+            for algorithm in ALGORITHMS {
+                prop_assert_eq!(&expected, &PingPongSolver::new(algorithm).solve(&graph));
+            }
+        }
+
+        #[test]
+        fn max_score_vertices_are_kings(size in 1..24i32, p in 0.0..=1.0f64, seed: u64) {
+            let graph = generate_ping_pong(size, p, &mut seeded_rng(seed, 0));
+            let scores = graph.score_sequence();
+            let max = scores.iter().max().copied();
+            let kings = PingPongSolver::new(naive_mul).solve(&graph);
+
+            for (v, score) in scores.iter().enumerate() {
+                if Some(*score) == max {
+                    prop_assert!(kings.contains(&(v as i32)), "vertex {} with score {} is not a king", v, score);
+                }
+            }
+            prop_assert!(kings.contains(&(graph.max_score_vertex(DrawSemantics::Ignore).unwrap() as i32)));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::matrix::matrix::Matrix;
+use crate::ping_pong::explanation::{explain_king, find_reach, Reach};
+
+/// Difference between a claimed set of kings and the kings of a graph.
+#[derive(Debug, PartialEq)]
+pub enum Discrepancy {
+    /// Claimed king that does not reach `unreached` in at most two steps.
+    Spurious { king: usize, unreached: usize },
+    /// King missing from the claim, with how it reaches every other vertex.
+    Missing { king: usize, witness: Vec<Reach> },
+}
+
+/// Checks a claimed set of kings vertex by vertex, without matrix multiplication,
+/// so the result does not depend on any multiplication method.
+pub fn verify_kings(adj: &Matrix<i32>, claimed: &[usize]) -> Vec<Discrepancy> {
+    (0..adj.cols())
+        .filter_map(|v| {
+            let unreached = (0..adj.cols()).find(|target| *target != v && find_reach(adj, v, *target).is_none());
+            match (claimed.contains(&v), unreached) {
+                (true, Some(unreached)) => Some(Discrepancy::Spurious { king: v, unreached }),
+                (false, None) => Some(Discrepancy::Missing { king: v, witness: explain_king(adj, v).expect("reaches everyone") }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::matrix::Matrix;
+    use crate::ping_pong::explanation::Reach;
+    use crate::ping_pong::verification::{verify_kings, Discrepancy};
+
+    #[test]
+    pub fn correct_claim_has_no_discrepancies() {
+        // 0 beats 1, 1 beats 2, 2 beats 0.
+        let adj = Matrix::with_data(vec![vec![0, 0, 1], vec![1, 0, 0], vec![0, 1, 0]]);
+
+        assert!(verify_kings(&adj, &[0, 1, 2]).is_empty());
+    }
+
+    #[test]
+    pub fn reports_missing_and_spurious_kings() {
+        // 0 beats 1 and 2, 1 beats 2.
+        let adj = Matrix::with_data(vec![vec![0, 0, 0], vec![1, 0, 0], vec![1, 1, 0]]);
+
+        assert_eq!(
+            vec![
+                Discrepancy::Missing { king: 0, witness: vec![Reach::Direct { target: 1 }, Reach::Direct { target: 2 }] },
+                Discrepancy::Spurious { king: 2, unreached: 0 },
+            ],
+            verify_kings(&adj, &[2])
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! End-to-end tests running the CLI on the fixtures in `res/` and comparing what it prints and
+//! writes with the expected outputs in `tests/golden/`. `{out}` in the arguments is replaced by a
+//! fresh output file, whose content is compared as well.
+//!
+//! After an intended change of behaviour, regenerate the expected outputs with
+//! `UPDATE_GOLDENS=1 cargo test --test golden` and review the diff.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const OUTPUT_FILE: &str = "{out}";
+
+/// Runs the CLI from the package root, returning its standard output and, if the arguments name
+/// an output file, its content.
+fn run(name: &str, args: &[&str]) -> (Vec<u8>, Option<Vec<u8>>) {
+    let out_file = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("golden-{}", name));
+    let _ = fs::remove_file(&out_file);
+    let writes_file = args.contains(&OUTPUT_FILE);
+    let args: Vec<String> = args.iter()
+        .map(|arg| arg.replace(OUTPUT_FILE, out_file.to_str().expect("UTF-8 temporary path")))
+        .collect();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ping-pong"))
+        .args(&args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("CLI runs");
+
+    assert!(output.status.success(), "ping-pong {} failed:\n{}", args.join(" "), String::from_utf8_lossy(&output.stderr));
+    let out = writes_file.then(|| fs::read(&out_file).unwrap_or_else(|e| panic!("ping-pong {} wrote no output file: {}", args.join(" "), e)));
+    (output.stdout, out)
+}
+
+/// Writes `content` to a temporary file and returns its path.
+fn temp_file(name: &str, content: &[u8]) -> String {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::write(&path, content).expect("temporary file is writable");
+    path.to_str().expect("UTF-8 temporary path").to_string()
+}
+
+/// Compares standard output with `<name>.stdout` and the output file, if the arguments name one, with `<name>.out`.
+fn run_golden(name: &str, args: &[&str]) {
+    let (stdout, out) = run(name, args);
+    compare(&golden(&format!("{}.stdout", name)), &stdout);
+    if let Some(out) = out {
+        compare(&golden(&format!("{}.out", name)), &out);
+    }
+}
+
+fn golden(file: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(file)
+}
+
+fn compare(path: &Path, actual: &[u8]) {
+    if env::var_os("UPDATE_GOLDENS").is_some() {
+        fs::write(path, actual).expect("golden is writable");
+        return;
+    }
+    let expected = fs::read(path)
+        .unwrap_or_else(|_| panic!("{} is missing, create it with UPDATE_GOLDENS=1", path.display()));
+    assert!(
+        expected == actual,
+        "{} differs from the output\n--- expected\n{}\n--- actual\n{}",
+        path.display(),
+        String::from_utf8_lossy(&expected),
+        String::from_utf8_lossy(actual)
+    );
+}
+
+#[test]
+pub fn solve_example() {
+    run_golden("solve_example", &["solve", "-i", "res/example.txt"]);
+}
+
+#[test]
+pub fn solve_example_to_file() {
+    let (stdout, out) = run("solve_example_to_file", &["solve", "-i", "res/example.txt", "-o", OUTPUT_FILE]);
+    let out = out.expect("solutions are written");
+
+    compare(&golden("solve_example_to_file.stdout"), &stdout);
+    compare(&golden("solve_example_to_file.out"), &out);
+    // `verify_example` checks the fixture, which is never rewritten, so it has to stay what `solve` writes.
+    let fixture = fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("res/result.txt")).expect("fixture is readable");
+    assert!(out == fixture, "res/result.txt differs from the solutions written for res/example.txt");
+}
+
+#[test]
+pub fn solve_example_explained() {
+    run_golden("solve_example_explained", &["solve", "-i", "res/example.txt", "--explain"]);
+}
+
+#[test]
+pub fn solve_example_verbose() {
+    run_golden("solve_example_verbose", &["solve", "-i", "res/example.txt", "--verbose"]);
+}
+
+#[test]
+pub fn solve_records_on_stdout_stay_machine_readable() {
+    // Timings differ between runs, so only the shape of the output is checked
+    let (stdout, _) = run("solve_json_verbose", &["solve", "-i", "res/example.txt", "--output-format", "json", "--verbose", "--explain"]);
+
+    let stdout = String::from_utf8(stdout).expect("UTF-8 output");
+    assert_eq!(2, stdout.lines().count());
+    for line in stdout.lines() {
+        serde_json::from_str::<serde_json::Value>(line).unwrap_or_else(|e| panic!("`{}` is not a JSON record: {}", line, e));
+    }
+}
+
+#[test]
+pub fn solve_generated() {
+    // Strassen alone takes half a minute on the 500 vertex graph in debug builds, it is covered by the other cases.
+    for method in ["--naive", "--mixed"] {
+        run_golden(&format!("solve_generated_{}", method.trim_start_matches("--")), &["solve", "-i", "res/generated.txt", method]);
+    }
+}
+
+#[test]
+pub fn solve_example_cross_checked() {
+    run_golden("solve_example_cross_checked", &["solve", "-i", "res/example.txt", "--cross-check"]);
+}
+
+#[test]
+pub fn verify_example_results() {
+    run_golden("verify_example", &["verify", "-i", "res/example.txt", "-r", "res/result.txt"]);
+}
+
+#[test]
+pub fn stats_of_fixtures() {
+    run_golden("stats_example", &["stats", "-i", "res/example.txt"]);
+    run_golden("stats_generated", &["stats", "-i", "res/generated.txt"]);
+}
+
+#[test]
+pub fn export_example_to_dot() {
+    run_golden("export_example", &["export", "-i", "res/example.txt", "-o", OUTPUT_FILE]);
+    run_golden("export_example_by_scores", &["export", "-i", "res/example.txt", "--layout", "scores", "-o", OUTPUT_FILE]);
+}
+
+#[test]
+pub fn convert_example_to_every_format() {
+    for format in ["matrix", "matches", "csv", "json", "binary"] {
+        let name = format!("convert_example_{}", format);
+        run_golden(&name, &["convert", "-i", "res/example.txt", "--output-format", format, "-o", OUTPUT_FILE]);
+    }
+}
+
+#[test]
+pub fn convert_round_trips_through_every_format() {
+    // Match lists name players by number, which every format has to keep apart from matrix entries.
+    let (_, matches) = run("round_trip_matches", &["convert", "-i", "res/example.txt", "--output-format", "matches", "-o", OUTPUT_FILE]);
+    let matches = temp_file("round_trip_matches.txt", &matches.expect("matches are written"));
+    let (_, expected) = run("round_trip_expected", &["convert", "-i", &matches, "--input-format", "matches", "-o", OUTPUT_FILE]);
+
+    for format in ["matrix", "matches", "csv", "json", "binary"] {
+        let (_, converted) = run("round_trip_converted", &["convert", "-i", &matches, "--input-format", "matches", "--output-format", format, "-o", OUTPUT_FILE]);
+        let converted = temp_file(&format!("round_trip.{}", format), &converted.expect("graphs are written"));
+        let (_, back) = run("round_trip_back", &["convert", "-i", &converted, "--input-format", format, "-o", OUTPUT_FILE]);
+        assert!(back == expected, "converting through {} changes the graphs", format);
+
+        let back = temp_file("round_trip_back.txt", &back.expect("graphs are written"));
+        run("round_trip_solved", &["solve", "-i", &back]);
+    }
+}
+
+#[test]
+pub fn generate_seeded() {
+    run_golden("generate_seeded", &["generate", "-s", "3..6", "--seed", "7", "--metadata", "-o", OUTPUT_FILE]);
+    for family in ["bradley-terry", "elo"] {
+        let name = format!("generate_seeded_{}", family);
+        run_golden(&name, &["generate", "-s", "8", "-f", family, "--seed", "7", "-o", OUTPUT_FILE]);
+    }
+}